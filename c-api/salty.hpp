@@ -6,23 +6,22 @@
 #include <cstdarg>
 #include <cstdint>
 #include <cstdlib>
+#include <ostream>
 #include <new>
 
-static const uintptr_t salty_COMPRESSED_Y_LENGTH = 32;
-static const uintptr_t salty_PUBLICKEY_SERIALIZED_LENGTH = 32;
-static const uintptr_t salty_FIELD_ELEMENT_LENGTH = 32;
-static const uintptr_t salty_SCALAR_LENGTH = 32;
-static const uintptr_t salty_SECRETKEY_NONCE_LENGTH = 32;
-static const uintptr_t salty_SECRETKEY_SCALAR_LENGTH = 32;
-static const uintptr_t salty_SECRETKEY_SEED_LENGTH = 32;
-static const uintptr_t salty_SECRETKEY_SERIALIZED_LENGTH = 32;
-static const uintptr_t salty_SHA256_LENGTH = 64;
-static const uintptr_t salty_SHA512_LENGTH = 64;
-static const uintptr_t salty_SIGNATURE_SERIALIZED_LENGTH = 64;
+constexpr static const uintptr_t salty_FIELD_ELEMENT_LENGTH = 32;
+
+constexpr static const uintptr_t salty_PUBLICKEY_SERIALIZED_LENGTH = 32;
+
+constexpr static const uintptr_t salty_SECRETKEY_SEED_LENGTH = 32;
+
+constexpr static const uintptr_t salty_SIGNATURE_SERIALIZED_LENGTH = 64;
+
+constexpr static const uintptr_t salty_SHA512_LENGTH = 64;
 
 /// Extensible error type for all `salty` operations.
 ///
-/// This enum has a hidden member, to prevent exhaustively checking for errors.
+/// This enum has a member `NoError` with value zero, for use in the C API.
 enum class salty_Error {
   /// Never occurs, simplifies C bindings
   NoError = 0,
@@ -32,27 +31,83 @@ enum class salty_Error {
   PublicKeyBytesInvalid,
   /// Signature verification failed
   SignatureInvalid,
-  /// Context for prehashed signatures too long
+  /// Context for signatures longer than 255 bytes
   ContextTooLong,
-  /// Point is on the other twist of curve
+  /// Point is on other twist of curve
   WrongTwist,
-  _Extensible,
+  /// Inputs to batch verification have different lengths
+  BatchLengthMismatch,
+  /// Scalar is not reduced modulo the group order
+  NonCanonicalScalar,
+  /// Public key bytes are not the canonical encoding of the point
+  NonCanonicalPublicKey,
+  /// Signature's R bytes are not the canonical encoding of the point
+  NonCanonicalR,
+  /// Public key is a point of small order
+  SmallOrderPublicKey,
+  /// Signature's R is a point of small order
+  SmallOrderR,
+  /// Message changed between the two passes of streaming signing
+  ReplayMismatch,
+  /// Public key does not belong to the secret key
+  PublicKeyMismatch,
+  /// Index for hardened-only key derivation does not have the hardened bit set
+  NonHardenedIndex,
+  /// Extended secret key bits violate the BIP32-Ed25519 constraints
+  InvalidExtendedSecretKey,
+  /// Hardened child keys can not be derived from public keys
+  HardenedPublicDerivation,
+  /// Public key has a component in the torsion subgroup
+  PublicKeyNotTorsionFree,
+  /// Signing was aborted, as a fault was detected
+  FaultDetected,
+  /// Compressed point does not decompress to a point on the curve
+  PointNotOnCurve,
+  /// Compressed point has x = 0, but its sign bit set
+  NegativeZero,
+  /// Bytes are not the canonical encoding of a Ristretto point
+  RistrettoBytesInvalid,
+  /// Requested output of `expand_message_xmd` is longer than 255 hash outputs
+  ExpandMessageTooLong,
+  /// Derived child key would be the neutral element, the index must be skipped
+  IdentityChild,
+  /// Scalars and points of a multiscalar multiplication have different lengths
+  MultiscalarLengthMismatch,
+  /// Scratch space has fewer entries than needed
+  ScratchTooSmall,
 };
 
 extern "C" {
 
 /// Generates a public key from a secret seed. Use to verify signatures.
+/// # Safety
+/// These are C-bindings
 void salty_public_key(const uint8_t (*seed)[salty_SECRETKEY_SEED_LENGTH],
                       uint8_t (*public_key)[salty_PUBLICKEY_SERIALIZED_LENGTH]);
 
 /// Signs the data, based on the keypair generated from the secret seed.
-void salty_sign(const uint8_t (*seed)[salty_SECRETKEY_SEED_LENGTH],
-                const uint8_t *data_ptr,
-                uintptr_t data_len,
-                uint8_t (*signature)[salty_SIGNATURE_SERIALIZED_LENGTH]);
+/// # Safety
+/// These are C-bindings
+salty_Error salty_sign(const uint8_t (*seed)[salty_SECRETKEY_SEED_LENGTH],
+                       const uint8_t *data_ptr,
+                       uintptr_t data_len,
+                       uint8_t (*signature)[salty_SIGNATURE_SERIALIZED_LENGTH]);
+
+/// Signs the data for a given context, based on the keypair generated
+/// from the secret seed.
+/// # Safety
+/// These are C-bindings
+salty_Error salty_sign_with_context(const uint8_t (*seed)[salty_SECRETKEY_SEED_LENGTH],
+                                    const uint8_t *data_ptr,
+                                    uintptr_t data_len,
+                                    const uint8_t *context_ptr,
+                                    uintptr_t context_len,
+                                    uint8_t (*signature)[salty_SIGNATURE_SERIALIZED_LENGTH]);
 
 /// Signs the prehashed data, based on the keypair generated from the secret seed.
 /// An optional context can also be passed (this is recommended).
+/// # Safety
+/// These are C-bindings
 salty_Error salty_sign_prehashed(const uint8_t (*seed)[salty_SECRETKEY_SEED_LENGTH],
                                  const uint8_t (*prehashed_data)[salty_SHA512_LENGTH],
                                  const uint8_t *context_ptr,
@@ -60,18 +115,35 @@ salty_Error salty_sign_prehashed(const uint8_t (*seed)[salty_SECRETKEY_SEED_LENG
                                  uint8_t (*signature)[salty_SIGNATURE_SERIALIZED_LENGTH]);
 
 /// Verify a presumed signature on the given data.
+/// # Safety
+/// These are C-bindings
 salty_Error salty_verify(const uint8_t (*public_key)[salty_PUBLICKEY_SERIALIZED_LENGTH],
                          const uint8_t *data_ptr,
                          uintptr_t data_len,
                          const uint8_t (*signature)[salty_SIGNATURE_SERIALIZED_LENGTH]);
 
 /// Verify a presumed signature on the given data.
+/// # Safety
+/// These are C-bindings
+salty_Error salty_verify_with_context(const uint8_t (*public_key)[salty_PUBLICKEY_SERIALIZED_LENGTH],
+                                      const uint8_t *data_ptr,
+                                      uintptr_t data_len,
+                                      const uint8_t (*signature)[salty_SIGNATURE_SERIALIZED_LENGTH],
+                                      const uint8_t *context_ptr,
+                                      uintptr_t context_len);
+
+/// Verify a presumed signature on the given data.
+/// # Safety
+/// These are C-bindings
 salty_Error salty_verify_prehashed(const uint8_t (*public_key)[salty_PUBLICKEY_SERIALIZED_LENGTH],
                                    const uint8_t (*prehashed_data)[salty_SHA512_LENGTH],
                                    const uint8_t (*signature)[salty_SIGNATURE_SERIALIZED_LENGTH],
                                    const uint8_t *context_ptr,
                                    uintptr_t context_len);
+
 /// Perform X25519 key agreement.
+/// # Safety
+/// These are C-bindings
 void salty_agree(const uint8_t (*scalar)[salty_SECRETKEY_SEED_LENGTH],
                  const uint8_t (*input_u)[salty_FIELD_ELEMENT_LENGTH],
                  uint8_t (*output_u)[salty_FIELD_ELEMENT_LENGTH]);
@@ -26,7 +26,7 @@ pub unsafe extern "C" fn salty_public_key(
     public_key: &mut [u8; PUBLICKEY_SERIALIZED_LENGTH],
 ) {
     let keypair = Keypair::from(seed);
-    public_key.copy_from_slice(keypair.public.compressed.as_bytes());
+    public_key.copy_from_slice(keypair.public().as_bytes());
 }
 
 #[no_mangle]
@@ -38,11 +38,15 @@ pub unsafe extern "C" fn salty_sign(
     data_ptr: *const u8,
     data_len: usize,
     signature: &mut [u8; SIGNATURE_SERIALIZED_LENGTH],
-) {
+) -> Error {
     let keypair = Keypair::from(seed);
     let data = core::slice::from_raw_parts(data_ptr, data_len);
 
-    signature.copy_from_slice(&keypair.sign(data).to_bytes());
+    match keypair.try_sign(data) {
+        Ok(sig) => signature.copy_from_slice(&sig.to_bytes()),
+        Err(error) => return error,
+    }
+    Error::NoError
 }
 
 #[no_mangle]
@@ -58,14 +62,14 @@ pub unsafe extern "C" fn salty_sign_with_context(
     context_len: usize,
     signature: &mut [u8; SIGNATURE_SERIALIZED_LENGTH],
 ) -> Error {
-    if context_len > 255 {
-        return Error::ContextTooLong;
-    }
     let keypair = Keypair::from(seed);
     let data = core::slice::from_raw_parts(data_ptr, data_len);
     let context = core::slice::from_raw_parts(context_ptr, context_len);
 
-    signature.copy_from_slice(&keypair.sign_with_context(data, context).to_bytes());
+    match keypair.try_sign_with_context(data, context) {
+        Ok(sig) => signature.copy_from_slice(&sig.to_bytes()),
+        Err(error) => return error,
+    }
     Error::NoError
 }
 
@@ -81,17 +85,13 @@ pub unsafe extern "C" fn salty_sign_prehashed(
     context_len: usize,
     signature: &mut [u8; SIGNATURE_SERIALIZED_LENGTH],
 ) -> Error {
-    if context_len > 255 {
-        return Error::ContextTooLong;
-    }
     let keypair = Keypair::from(seed);
     let context = core::slice::from_raw_parts(context_ptr, context_len);
 
-    signature.copy_from_slice(
-        &keypair
-            .sign_prehashed(prehashed_data, Some(context))
-            .to_bytes(),
-    );
+    match keypair.try_sign_prehashed(prehashed_data, Some(context)) {
+        Ok(sig) => signature.copy_from_slice(&sig.to_bytes()),
+        Err(error) => return error,
+    }
 
     Error::NoError
 }
@@ -134,9 +134,6 @@ pub unsafe extern "C" fn salty_verify_with_context(
     context_ptr: *const u8,
     context_len: usize,
 ) -> Error {
-    if context_len > 255 {
-        return Error::ContextTooLong;
-    }
     let maybe_public_key = PublicKey::try_from(public_key);
     if maybe_public_key.is_err() {
         return maybe_public_key.err().unwrap();
@@ -164,9 +161,6 @@ pub unsafe extern "C" fn salty_verify_prehashed(
     context_ptr: *const u8,
     context_len: usize,
 ) -> Error {
-    if context_len > 255 {
-        return Error::ContextTooLong;
-    }
     let maybe_public_key = PublicKey::try_from(public_key);
     if maybe_public_key.is_err() {
         return maybe_public_key.err().unwrap();
@@ -63,21 +63,22 @@ fn run_eddsa_verify(test_key: &Key, test_data: &SignatureTestVector) {
     let pk = <[u8; PUBLICKEY_SERIALIZED_LENGTH]>::try_from(test_key.pk);
     let sig = <[u8; SIGNATURE_SERIALIZED_LENGTH]>::try_from(test_data.sig);
 
-    let valid = match (pk, sig) {
+    let (valid, strictly_valid) = match (pk, sig) {
         (Ok(pk), Ok(sig)) => match PublicKey::try_from(&pk) {
             Ok(pk) => {
                 let sig = Signature::from(&sig);
                 let result = pk.verify(test_data.msg, &sig);
-                result.is_ok()
+                let strict_result = pk.verify_strict(test_data.msg, &sig);
+                (result.is_ok(), strict_result.is_ok())
             }
-            _ => false,
+            _ => (false, false),
         },
-        _ => false,
+        _ => (false, false),
     };
 
     match test_data.result {
         ExpectedResult::Valid => {
-            if !valid {
+            if !(valid && strictly_valid) {
                 hprintln!("FAIL (expected VALID, but isn't)");
                 fail();
             } else {
@@ -85,7 +86,10 @@ fn run_eddsa_verify(test_key: &Key, test_data: &SignatureTestVector) {
             }
         }
         ExpectedResult::Invalid => {
-            if valid {
+            if strictly_valid {
+                hprintln!("FAIL (expected strictly INVALID, but isn't)");
+                fail();
+            } else if valid {
                 if test_data.flags.contains(&"SignatureMalleability") {
                     hprintln!("ALLOW FAIL for SignatureMalleability (expected INVALID, but isn't)");
                 } else {
@@ -0,0 +1,113 @@
+#!/usr/bin/env python3
+"""Generate `src/edwards/basepoint_tables.rs`, the precomputed basepoint tables.
+
+With `n` tables, table `j` holds the multiples `B_j, 2B_j, ..., 8B_j`
+of `B_j = 16^(j * 64/n) B`, as affine Niels points `(y + x, y - x, 2dxy)`.
+
+In addition, the NAF table holds the odd multiples `B, 3B, ..., 15B`,
+for variable-time multiplication with width-5 NAFs, and the spaced NAF
+table `j` holds the odd multiples of `2^(32 * j) B`, for the variable-time
+multiplication of prepared public keys with fewer doublings.
+
+Usage: python3 scripts/basepoint_tables.py > src/edwards/basepoint_tables.rs
+"""
+
+p = 2**255 - 19
+d = -121665 * pow(121666, p - 2, p) % p
+
+B = (
+    15112221349535400772501151409588531511454012693041857206046113283949847762202,
+    46316835694926478169428394003475163141307993866256225615783033603165251855960,
+)
+
+
+def add(P, Q):
+    (x1, y1), (x2, y2) = P, Q
+    t = d * x1 * x2 * y1 * y2
+    x3 = (x1 * y2 + x2 * y1) * pow(1 + t, p - 2, p)
+    y3 = (y1 * y2 + x1 * x2) * pow(1 - t, p - 2, p)
+    return (x3 % p, y3 % p)
+
+
+def mul(n, P):
+    Q = (0, 1)
+    while n:
+        if n & 1:
+            Q = add(Q, P)
+        P = add(P, P)
+        n >>= 1
+    return Q
+
+
+def niels(P):
+    x, y = P
+    return [(y + x) % p, (y - x) % p, 2 * d * x * y % p]
+
+
+def field_element(x):
+    data = ", ".join("0x%02x" % b for b in x.to_bytes(32, "little"))
+    return "            [%s]," % data
+
+
+FEATURES = [
+    (1, "not(any(feature = \"basepoint-table-6k\", feature = \"basepoint-table-24k\"))"),
+    (8, "all(feature = \"basepoint-table-6k\", not(feature = \"basepoint-table-24k\"))"),
+    (32, "feature = \"basepoint-table-24k\""),
+]
+
+print("// generated by `scripts/basepoint_tables.py`, do not edit")
+print("//")
+print("// table `j` holds `B_j, 2B_j, ..., 8B_j`, with `B_j = 16^(j * 64/TABLES) B`,")
+print("// as affine Niels points `(y + x, y - x, 2dxy)`;")
+print("// the NAF table holds `B, 3B, ..., 15B`, for width-5 NAFs, and")
+print("// spaced NAF table `j` holds the odd multiples of `2^(32 * j) B`")
+for n, cfg in FEATURES:
+    print()
+    print("#[cfg(%s)]" % cfg)
+    print("pub(super) const TABLES: usize = %d;" % n)
+    print()
+    print("#[cfg(%s)]" % cfg)
+    print("#[rustfmt::skip]")
+    print("pub(super) static BASEPOINT_TABLES: [[[[u8; 32]; 3]; 8]; TABLES] = [")
+    for j in range(n):
+        base = mul(16 ** (j * 64 // n), B)
+        print("    [")
+        multiple = base
+        for _ in range(8):
+            print("        [")
+            for x in niels(multiple):
+                print(field_element(x))
+            print("        ],")
+            multiple = add(multiple, base)
+        print("    ],")
+    print("];")
+
+print()
+print("#[rustfmt::skip]")
+print("pub(super) static BASEPOINT_NAF_TABLE: [[[u8; 32]; 3]; 8] = [")
+double = add(B, B)
+multiple = B
+for _ in range(8):
+    print("    [")
+    for x in niels(multiple):
+        print(field_element(x)[4:])
+    print("    ],")
+    multiple = add(multiple, double)
+print("];")
+
+print()
+print("#[rustfmt::skip]")
+print("pub(super) static SPACED_BASEPOINT_NAF_TABLES: [[[[u8; 32]; 3]; 8]; 8] = [")
+for j in range(8):
+    base = mul(2 ** (32 * j), B)
+    print("    [")
+    double = add(base, base)
+    multiple = base
+    for _ in range(8):
+        print("        [")
+        for x in niels(multiple):
+            print(field_element(x))
+        print("        ],")
+        multiple = add(multiple, double)
+    print("    ],")
+print("];")
@@ -4,6 +4,7 @@ use crate::{
     field::{FieldElement, FieldImplementation as _},
     montgomery::MontgomeryPoint,
     scalar::Scalar,
+    signature, Error, Result,
 };
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
@@ -39,6 +40,39 @@ impl<'a> From<&'a SecretKey> for PublicKey {
     }
 }
 
+impl TryFrom<&signature::PublicKey> for PublicKey {
+    type Error = Error;
+
+    /// Convert an Ed25519 public key to its X25519 counterpart, like libsodium's
+    /// `crypto_sign_ed25519_pk_to_curve25519`.
+    ///
+    /// Points of small order, and points with a small order component, are rejected.
+    fn try_from(public: &signature::PublicKey) -> Result<PublicKey> {
+        if public.point.is_small_order() {
+            return Err(Error::SmallOrderPublicKey);
+        }
+        if !public.point.is_torsion_free() {
+            return Err(Error::PublicKeyNotTorsionFree);
+        }
+        Ok(PublicKey(public.point.to_montgomery()))
+    }
+}
+
+impl From<&signature::SecretKey> for SecretKey {
+    /// Convert an Ed25519 secret key to its X25519 counterpart, like libsodium's
+    /// `crypto_sign_ed25519_sk_to_curve25519`: this is the (clamped) secret scalar.
+    fn from(secret: &signature::SecretKey) -> SecretKey {
+        SecretKey(secret.scalar.clone())
+    }
+}
+
+impl From<&signature::Keypair> for SecretKey {
+    /// Convert the secret key of an Ed25519 keypair to its X25519 counterpart.
+    fn from(keypair: &signature::Keypair) -> SecretKey {
+        SecretKey::from(&keypair.secret)
+    }
+}
+
 impl PublicKey {
     /// Convert this public key to a byte array.
     #[inline]
@@ -225,6 +259,53 @@ mod tests {
         // }
     }
 
+    #[test]
+    fn ed25519_conversion() {
+        use crate::edwards::CompressedY;
+        use hex_literal::hex;
+
+        // test vector from libsodium's `ed25519_convert` test
+        let keypair = signature::Keypair::from(&hex!(
+            "421151a459faeade3d247115f94aedae42318124095afabe4d1451a559faedee"
+        ));
+        let public = PublicKey::try_from(&keypair.public).unwrap();
+        let secret = SecretKey::from(&keypair);
+        assert_eq!(
+            public.to_bytes(),
+            hex!("f1814f0e8ff1043d8a44d25babff3cedcae6c22c3edaa48f857ae70de2baae50")
+        );
+        assert_eq!(
+            secret.to_bytes(),
+            hex!("8052030376d47112be7f73ed7a019293dd12ad910b654455798b4667d73de166")
+        );
+        assert_eq!(secret.public(), public);
+
+        let other = signature::Keypair::from(&[42u8; 32]);
+        let other_public = PublicKey::try_from(&other.public).unwrap();
+        assert_eq!(
+            secret.agree(&other_public).to_bytes(),
+            SecretKey::from(&other.secret).agree(&public).to_bytes(),
+        );
+
+        // the point (0, -1) of order two
+        let order_two = CompressedY(hex!(
+            "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f"
+        ))
+        .decompressed()
+        .unwrap();
+        let small_order = signature::PublicKey::try_from(&order_two.compressed().0).unwrap();
+        assert_eq!(
+            PublicKey::try_from(&small_order),
+            Err(Error::SmallOrderPublicKey)
+        );
+        let mixed_order = &keypair.public.point + &order_two;
+        let mixed_order = signature::PublicKey::try_from(&mixed_order.compressed().0).unwrap();
+        assert_eq!(
+            PublicKey::try_from(&mixed_order),
+            Err(Error::PublicKeyNotTorsionFree)
+        );
+    }
+
     #[test]
     fn zeroize_on_drop() {
         let mut secret = SecretKey::from_seed(&[1u8; 32]);
@@ -24,5 +24,11 @@ pub const PUBLICKEY_SERIALIZED_LENGTH: usize = 32;
 /// the length of a secret key when serialized
 pub const SECRETKEY_SERIALIZED_LENGTH: usize = 32;
 
+/// the length of an expanded secret key (scalar and nonce) when serialized
+pub const SECRETKEY_EXPANDED_LENGTH: usize = 64;
+
+/// the length of a keypair (seed and public key) when serialized
+pub const KEYPAIR_SERIALIZED_LENGTH: usize = 64;
+
 /// the length of a signature when serialized
 pub const SIGNATURE_SERIALIZED_LENGTH: usize = 64;
@@ -1,9 +1,10 @@
 use core::{
     cmp::PartialEq,
-    ops::{Add, Mul, Neg},
+    ops::{Add, Mul, Neg, Sub},
 };
 
-use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use zeroize::Zeroize;
 
 use crate::{
     constants::COMPRESSED_Y_LENGTH,
@@ -13,11 +14,31 @@ use crate::{
     Error, Result,
 };
 
-/// These represent the (X,Y,Z,T) coordinates
+mod basepoint_tables;
+use basepoint_tables::{
+    BASEPOINT_NAF_TABLE, BASEPOINT_TABLES, SPACED_BASEPOINT_NAF_TABLES, TABLES,
+};
+
+mod multiscalar;
+pub use multiscalar::{MultiscalarScratch, PIPPENGER_THRESHOLD};
+
+/// A point on the Edwards curve, in extended coordinates (X,Y,Z,T)
+///
+/// The curve group has order `8 * ell`: the points used in Ed25519 lie in the
+/// subgroup of prime order `ell` generated by the basepoint, while e.g. points
+/// decoded from untrusted bytes may have a component in the torsion subgroup
+/// of order eight. See `is_small_order` and `is_torsion_free` to check for this,
+/// and `mul_by_cofactor` to clear the torsion component.
+///
+/// The group operations, i.e., `Add`, `Sub`, `Neg`, `double`, `Scalar * EdwardsPoint`
+/// and `mul_base`, as well as compression, comparison (`ConstantTimeEq`), selection
+/// (`ConditionallySelectable`) and negation (`subtle::ConditionallyNegatable`)
+/// run in constant time, so they can be used with secret points and scalars.
+/// The checks return a `bool`, but compute it in constant time.
 #[derive(Clone, Copy, Debug, Default)]
 pub struct EdwardsPoint(
     // TODO: maybe label them properly
-    [FieldElement; 4],
+    pub(crate) [FieldElement; 4],
 );
 
 /// "Compressed" form of a `EdwardsPoint`, whereby
@@ -26,6 +47,25 @@ pub struct EdwardsPoint(
 #[derive(Clone, Copy, Debug, Default)]
 pub struct CompressedY(pub [u8; 32]);
 
+/// A point in affine Niels coordinates `(y + x, y - x, 2dxy)`,
+/// which are cheaper to add to an `EdwardsPoint` than another `EdwardsPoint`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct AffineNielsPoint {
+    y_plus_x: FieldElement,
+    y_minus_x: FieldElement,
+    xy2d: FieldElement,
+}
+
+/// A point in cached coordinates `(Y + X, Y - X, Z, 2dT)`,
+/// for repeated additions of the same `EdwardsPoint`.
+#[derive(Clone, Copy, Debug, Default, Zeroize)]
+pub(crate) struct CachedPoint {
+    y_plus_x: FieldElement,
+    y_minus_x: FieldElement,
+    z: FieldElement,
+    t2d: FieldElement,
+}
+
 impl From<&[u8; COMPRESSED_Y_LENGTH]> for CompressedY {
     fn from(bytes: &[u8; COMPRESSED_Y_LENGTH]) -> CompressedY {
         CompressedY(*bytes)
@@ -86,6 +126,74 @@ impl CompressedY {
         let T = &X * &Y;
         Ok(EdwardsPoint([X, Y, Z, T]))
     }
+
+    /// Decompress in constant time, for secret points.
+    ///
+    /// This follows `decompressed`, but uses `Choice` and conditional selection
+    /// instead of branches, and returns a `CtOption` that is none exactly
+    /// when `decompressed` returns an error.
+    pub fn decompressed_ct(&self) -> CtOption<EdwardsPoint> {
+        #![allow(non_snake_case)]
+
+        let Y = FieldElement::from_bytes_unchecked(self.as_bytes());
+        let Z = FieldElement::ONE;
+        let Y_squared = Y.squared();
+
+        let u = &Y_squared - &Z; // y**2 - 1
+        let v = &(&Y_squared * &FieldElement::D) + &Z; // dy**2 + 1
+
+        let v2 = v.squared();
+        let v4 = v2.squared();
+        let v7 = &(&v4 * &v2) * &v;
+
+        let t = &v7 * &u;
+        let mut X = &(&(&t.pow2523() * &u) * &v2) * &v;
+
+        let chk = &X.squared() * &v;
+        let X_times_I = &X * &FieldElement::I;
+        X.conditional_assign(&X_times_I, !chk.ct_eq(&u));
+
+        let chk = &X.squared() * &v;
+        let is_on_curve = chk.ct_eq(&u);
+
+        let minus_X = -&X;
+        X.conditional_assign(&minus_X, !X.parity().ct_eq(&(self.0[31] >> 7)));
+
+        let T = &X * &Y;
+        CtOption::new(EdwardsPoint([X, Y, Z, T]), is_on_curve)
+    }
+
+    /// Decompress, rejecting all non-canonical encodings: the y-coordinate must be
+    /// reduced (`Error::NonCanonicalFieldElement`), the point must lie on the curve
+    /// (`Error::PointNotOnCurve`), and the sign bit may not be set if x is zero
+    /// (`Error::NegativeZero`).
+    ///
+    /// In other words, this accepts exactly the encodings produced by
+    /// `EdwardsPoint::compressed`, while `decompressed` also accepts the others.
+    pub fn decompressed_canonical(&self) -> Result<EdwardsPoint> {
+        self.check_canonical()?;
+        self.decompressed().map_err(|_| Error::PointNotOnCurve)
+    }
+
+    /// Check whether these bytes are the canonical encoding of their point,
+    /// assuming they decompress at all.
+    pub(crate) fn is_canonical(&self) -> bool {
+        self.check_canonical().is_ok()
+    }
+
+    /// The y-coordinate must be reduced, and the sign bit may not be set if x is zero,
+    /// i.e., if y is one or minus one.
+    fn check_canonical(&self) -> Result {
+        let mut y_bytes = self.0;
+        y_bytes[31] &= 0x7f;
+        let y = FieldElement::from_bytes(&y_bytes)?;
+
+        let x_is_zero = y == FieldElement::ONE || y == -&FieldElement::ONE;
+        if x_is_zero && (self.0[31] >> 7) == 1 {
+            return Err(Error::NegativeZero);
+        }
+        Ok(())
+    }
     // static int unpackneg(gf r[4],const u8 p[32]) {
     //   // "load curve point"
     //   gf t, chk, num, den, den2, den4, den6;
@@ -200,29 +308,382 @@ impl EdwardsPoint {
 
         &(&y + &one) * &(&one - &y).inverse()
     }
+
+    /// Check whether this is the neutral element, without normalizing
+    /// the projective coordinates.
+    ///
+    /// The all-zero coordinates of `EdwardsPoint::default()` are not a point,
+    /// in particular not the neutral element.
+    pub fn is_identity(&self) -> bool {
+        let p = &self.0;
+        (p[0].ct_eq(&FieldElement::ZERO) & p[1].ct_eq(&p[2]) & !p[2].ct_eq(&FieldElement::ZERO))
+            .into()
+    }
+
+    /// Compute `2 * self`, with the dedicated doubling formula of
+    /// [Hisil, Wong, Carter and Dawson, section 3.3][hwcd], which is cheaper than addition.
+    ///
+    /// [hwcd]: https://eprint.iacr.org/2008/522
+    pub fn double(&self) -> EdwardsPoint {
+        let p = &self.0;
+
+        let a = p[0].squared(); // A <- X1^2
+        let b = p[1].squared(); // B <- Y1^2
+        let c = p[2].squared();
+        let c = &c + &c; // C <- 2*Z1^2
+        let d = -&a; // D <- a*A, with a = -1
+
+        let e = &p[0] + &p[1];
+        let e = &(&e.squared() - &a) - &b; // E <- (X1 + Y1)^2 - A - B
+        let g = &d + &b;
+        let f = &g - &c;
+        let h = &d - &b;
+
+        EdwardsPoint([&e * &f, &g * &h, &f * &g, &e * &h])
+    }
+
+    /// Multiply by the cofactor eight, by doubling three times.
+    pub fn mul_by_cofactor(&self) -> EdwardsPoint {
+        self.double().double().double()
+    }
+
+    /// Check whether this point lies in the torsion subgroup of order eight.
+    pub fn is_small_order(&self) -> bool {
+        self.mul_by_cofactor().is_identity()
+    }
+
+    /// Check whether this point lies in the prime order subgroup,
+    /// i.e., has no component in the torsion subgroup.
+    pub fn is_torsion_free(&self) -> bool {
+        (&Scalar(Scalar::ell()) * self).is_identity()
+    }
+
+    /// Check whether this point lies in the prime order subgroup, like `is_torsion_free`.
+    ///
+    /// This is *not* constant-time, only use it on public inputs.
+    pub(crate) fn vartime_is_torsion_free(&self) -> bool {
+        EdwardsPoint::vartime_double_scalar_mul_basepoint(
+            &Scalar(Scalar::ell()),
+            self,
+            &Scalar::default(),
+        )
+        .is_identity()
+    }
+
+    /// Compute `a * A + b * B` for the basepoint `B`, as needed for verification,
+    /// in about half the time of two separate multiplications.
+    ///
+    /// The odd multiples `A, 3A, ..., 15A` are built on the stack: eight cached points,
+    /// which take 4 KiB with the `tweetnacl` field (on targets other than Cortex-M4
+    /// and Cortex-M33), and 1 KiB with the `haase` field. The multiples of `B` are
+    /// precomputed, and decoded one at a time.
+    ///
+    /// This is *not* constant-time, only use it on public inputs.
+    pub(crate) fn vartime_double_scalar_mul_basepoint(
+        a: &Scalar,
+        point: &EdwardsPoint,
+        b: &Scalar,
+    ) -> EdwardsPoint {
+        NafLookupTable::<8>::new(point).vartime_double_scalar_mul_basepoint(a, b)
+    }
+
+    /// Compute `scalar * B` for the basepoint `B`, in constant time.
+    ///
+    /// This uses precomputed tables of multiples of the basepoint, which are
+    /// much faster than the generic `Scalar * EdwardsPoint`. Their size is chosen
+    /// by the cargo features: 768 bytes by default, 6 KiB with `basepoint-table-6k`,
+    /// or 24 KiB with `basepoint-table-24k`.
+    pub fn mul_base(scalar: &Scalar) -> EdwardsPoint {
+        // table `j` contains multiples of `16^(j * SPACING) B`
+        const SPACING: usize = 64 / TABLES;
+
+        let mut digits = scalar.reduce().as_radix_16();
+
+        // `sum(16^i * sum(digits[j * SPACING + i] * 16^(j * SPACING) B))`
+        let mut sum = EdwardsPoint::neutral_element();
+        for i in (0..SPACING).rev() {
+            if i != SPACING - 1 {
+                for _ in 0..4 {
+                    sum = sum.double();
+                }
+            }
+            for (j, table) in BASEPOINT_TABLES.iter().enumerate() {
+                sum = &sum + &AffineNielsPoint::select(table, digits[j * SPACING + i]);
+            }
+        }
+
+        digits.zeroize();
+        sum
+    }
 }
 
-impl<'a, 'b> Add<&'b EdwardsPoint> for &'a EdwardsPoint {
+/// The odd multiples `P, 3P, 5P, ..., (2N-1)P` of a point in cached coordinates,
+/// for multiplication by scalars in width-`w` non-adjacent form, where `N = 2^(w-2)`.
+///
+/// `N` must be a power of two, at most 64, which `new` checks at compile time.
+#[derive(Clone, Debug)]
+pub(crate) struct NafLookupTable<const N: usize>(pub(crate) [CachedPoint; N]);
+
+impl<const N: usize> NafLookupTable<N> {
+    /// The NAF width `w` matching the size of the table.
+    pub(crate) const WIDTH: usize = N.trailing_zeros() as usize + 2;
+
+    const VALID_SIZE: () = assert!(
+        N.is_power_of_two() && N <= 64,
+        "NAF lookup tables have a power of two size, at most 64"
+    );
+
+    pub(crate) fn new(point: &EdwardsPoint) -> Self {
+        let () = Self::VALID_SIZE;
+
+        let double = CachedPoint::from(&point.double());
+        let mut multiple = *point;
+        let mut table = [CachedPoint::from(point); N];
+        for entry in table.iter_mut().skip(1) {
+            multiple = &multiple + &double;
+            *entry = CachedPoint::from(&multiple);
+        }
+        NafLookupTable(table)
+    }
+
+    /// Compute `digit * P` for an odd digit with absolute value below `2N`.
+    pub(crate) fn select(&self, digit: i8) -> CachedPoint {
+        if digit > 0 {
+            self.0[digit as usize / 2]
+        } else {
+            -&self.0[(-digit) as usize / 2]
+        }
+    }
+
+    /// Compute `a * P + b * B` for the basepoint `B`, sharing the doublings
+    /// between the two terms, and using a width-`w` NAF for `a`, and a width-5 NAF
+    /// with the precomputed odd multiples of `B` for `b`.
+    ///
+    /// This is *not* constant-time, only use it on public inputs.
+    pub(crate) fn vartime_double_scalar_mul_basepoint(
+        &self,
+        a: &Scalar,
+        b: &Scalar,
+    ) -> EdwardsPoint {
+        let a_naf = a.non_adjacent_form(Self::WIDTH);
+        let b_naf = b.reduce().non_adjacent_form(5);
+
+        // skip the leading zeros
+        let top = (0..256)
+            .rev()
+            .find(|&i| a_naf[i] != 0 || b_naf[i] != 0)
+            .unwrap_or(0);
+
+        let mut sum = EdwardsPoint::neutral_element();
+        for i in (0..=top).rev() {
+            sum = sum.double();
+            if a_naf[i] != 0 {
+                sum = &sum + &self.select(a_naf[i]);
+            }
+            if b_naf[i] != 0 {
+                sum = &sum + &AffineNielsPoint::vartime_select(&BASEPOINT_NAF_TABLE, b_naf[i]);
+            }
+        }
+        sum
+    }
+}
+
+/// Tables of odd multiples of the spaced points `P_j = 2^(j * 256/N) P`, for `j < N`,
+/// for variable-time multiplication with `256/N` instead of 256 doublings.
+///
+/// `N` must be a power of two, at most 8, which `new` checks at compile time.
+#[derive(Clone, Debug)]
+pub(crate) struct SpacedNafTables<const N: usize>([NafLookupTable<8>; N]);
+
+impl<const N: usize> SpacedNafTables<N> {
+    /// The distance between the digits of a scalar multiplying the same table.
+    const SPACING: usize = 256 / N;
+
+    const VALID_SIZE: () = assert!(
+        N.is_power_of_two() && N <= 8,
+        "spaced NAF tables come in a power of two number, at most 8"
+    );
+
+    pub(crate) fn new(point: &EdwardsPoint) -> Self {
+        let () = Self::VALID_SIZE;
+
+        let mut spaced = *point;
+        SpacedNafTables(core::array::from_fn(|j| {
+            if j > 0 {
+                for _ in 0..Self::SPACING {
+                    spaced = spaced.double();
+                }
+            }
+            NafLookupTable::new(&spaced)
+        }))
+    }
+
+    /// Compute `a * P + b * B` for the basepoint `B`, splitting the width-5 NAFs
+    /// of both scalars into `N` parts, which multiply the spaced multiples of `P`,
+    /// and the precomputed spaced multiples of `B`.
+    ///
+    /// This is *not* constant-time, only use it on public inputs.
+    pub(crate) fn vartime_double_scalar_mul_basepoint(
+        &self,
+        a: &Scalar,
+        b: &Scalar,
+    ) -> EdwardsPoint {
+        let a_naf = a.non_adjacent_form(5);
+        let b_naf = b.reduce().non_adjacent_form(5);
+
+        let mut sum = EdwardsPoint::neutral_element();
+        for i in (0..Self::SPACING).rev() {
+            sum = sum.double();
+            for (j, table) in self.0.iter().enumerate() {
+                let digit = a_naf[j * Self::SPACING + i];
+                if digit != 0 {
+                    sum = &sum + &table.select(digit);
+                }
+                // the basepoint tables are spaced by 32 bits
+                let digit = b_naf[j * Self::SPACING + i];
+                if digit != 0 {
+                    let basepoint_table = &SPACED_BASEPOINT_NAF_TABLES[j * Self::SPACING / 32];
+                    sum = &sum + &AffineNielsPoint::vartime_select(basepoint_table, digit);
+                }
+            }
+        }
+        sum
+    }
+}
+
+impl AffineNielsPoint {
+    /// Compute `digit * P` for an odd digit with absolute value below 16,
+    /// from the table of odd multiples `P, 3P, ..., 15P`, decoding only the entry used.
+    fn vartime_select(table: &[[[u8; 32]; 3]; 8], digit: i8) -> AffineNielsPoint {
+        let point = AffineNielsPoint::from_bytes(&table[digit.unsigned_abs() as usize / 2]);
+        if digit > 0 {
+            point
+        } else {
+            -&point
+        }
+    }
+
+    fn from_bytes(bytes: &[[u8; 32]; 3]) -> AffineNielsPoint {
+        AffineNielsPoint {
+            y_plus_x: FieldElement::from_bytes_unchecked(&bytes[0]),
+            y_minus_x: FieldElement::from_bytes_unchecked(&bytes[1]),
+            xy2d: FieldElement::from_bytes_unchecked(&bytes[2]),
+        }
+    }
+
+    /// Compute `digit * P` in constant time, for `-8 <= digit <= 8`,
+    /// from the table of multiples `P, 2P, ..., 8P`.
+    fn select(table: &[[[u8; 32]; 3]; 8], digit: i8) -> AffineNielsPoint {
+        let negative = (digit as u8) >> 7;
+        let absolute = (digit - ((-(negative as i8)) & digit) * 2) as u8;
+
+        // start with the neutral element `(1, 1, 0)`
+        let mut one = [0u8; 32];
+        one[0] = 1;
+        let mut bytes = [one, one, [0u8; 32]];
+
+        for (i, entry) in table.iter().enumerate() {
+            let choice = absolute.ct_eq(&(i as u8 + 1));
+            for (coordinate, entry) in bytes.iter_mut().zip(entry.iter()) {
+                for (byte, entry) in coordinate.iter_mut().zip(entry.iter()) {
+                    byte.conditional_assign(entry, choice);
+                }
+            }
+        }
+
+        let mut point = AffineNielsPoint::from_bytes(&bytes);
+        bytes.zeroize();
+
+        // negation swaps `y + x` and `y - x`, and negates `2dxy`
+        let negative = Choice::from(negative);
+        FieldElement::conditional_swap(&mut point.y_plus_x, &mut point.y_minus_x, negative);
+        let minus_xy2d = -&point.xy2d;
+        point.xy2d.conditional_assign(&minus_xy2d, negative);
+
+        point
+    }
+}
+
+impl Add<&AffineNielsPoint> for &EdwardsPoint {
     type Output = EdwardsPoint;
 
-    fn add(self, other: &'b EdwardsPoint) -> Self::Output {
+    fn add(self, other: &AffineNielsPoint) -> EdwardsPoint {
         let p = &self.0;
-        let q = &other.0;
 
         let a = &p[1] - &p[0];
-        let t = &q[1] - &q[0];
-        let a = &a * &t; // A <- (Y1 - X1)(Y2 - X2)
+        let a = &a * &other.y_minus_x; // A <- (Y1 - X1)(y2 - x2)
+
+        let b = &p[1] + &p[0];
+        let b = &b * &other.y_plus_x; // B <- (Y1 + X1)(y2 + x2)
 
-        // let mut b = &p[0] + &p[1];
-        let b = &p[0] + &p[1];
-        let t = &q[0] + &q[1];
-        let b = &b * &t; // B <- (Y1 + X1)*(Y2 + X2)
-                         // b *= &t;
+        let c = &p[3] * &other.xy2d; // C <- 2d*T1*x2*y2
+
+        let d = &p[2] + &p[2]; // D <- 2*Z1
+
+        let e = &b - &a;
+        let f = &d - &c;
+        let g = &d + &c;
+        let h = &b + &a;
+
+        EdwardsPoint([&e * &f, &h * &g, &g * &f, &e * &h])
+    }
+}
 
-        let c = &p[3] * &q[3];
-        let c = &c * &FieldElement::D2; // C <- k*T1*T2  with k = 2d' =
+impl Neg for &AffineNielsPoint {
+    type Output = AffineNielsPoint;
 
-        let d = &p[2] * &q[2];
+    /// negation swaps `y + x` and `y - x`, and negates `2dxy`
+    fn neg(self) -> AffineNielsPoint {
+        AffineNielsPoint {
+            y_plus_x: self.y_minus_x,
+            y_minus_x: self.y_plus_x,
+            xy2d: -&self.xy2d,
+        }
+    }
+}
+
+impl From<&EdwardsPoint> for CachedPoint {
+    fn from(point: &EdwardsPoint) -> CachedPoint {
+        let p = &point.0;
+        CachedPoint {
+            y_plus_x: &p[1] + &p[0],
+            y_minus_x: &p[1] - &p[0],
+            z: p[2],
+            t2d: &p[3] * &FieldElement::D2,
+        }
+    }
+}
+
+impl Neg for &CachedPoint {
+    type Output = CachedPoint;
+
+    /// negation swaps `Y + X` and `Y - X`, and negates `2dT`
+    fn neg(self) -> CachedPoint {
+        CachedPoint {
+            y_plus_x: self.y_minus_x,
+            y_minus_x: self.y_plus_x,
+            z: self.z,
+            t2d: -&self.t2d,
+        }
+    }
+}
+
+impl Add<&CachedPoint> for &EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    /// the "add-2008-hwcd-3" formula of Hisil, Wong, Carter and Dawson
+    fn add(self, other: &CachedPoint) -> EdwardsPoint {
+        let p = &self.0;
+
+        let a = &p[1] - &p[0];
+        let a = &a * &other.y_minus_x; // A <- (Y1 - X1)(Y2 - X2)
+
+        let b = &p[1] + &p[0];
+        let b = &b * &other.y_plus_x; // B <- (Y1 + X1)(Y2 + X2)
+
+        let c = &p[3] * &other.t2d; // C <- 2d*T1*T2
+
+        let d = &p[2] * &other.z;
         let d = &d + &d; // D <- 2*Z1*Z2
 
         let e = &b - &a;
@@ -230,9 +691,23 @@ impl<'a, 'b> Add<&'b EdwardsPoint> for &'a EdwardsPoint {
         let g = &d + &c;
         let h = &b + &a;
 
-        let coordinates = [&e * &f, &h * &g, &g * &f, &e * &h];
+        EdwardsPoint([&e * &f, &h * &g, &g * &f, &e * &h])
+    }
+}
+
+impl<'a, 'b> Add<&'b EdwardsPoint> for &'a EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    fn add(self, other: &'b EdwardsPoint) -> Self::Output {
+        self + &CachedPoint::from(other)
+    }
+}
 
-        EdwardsPoint(coordinates)
+impl Sub<&EdwardsPoint> for &EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    fn sub(self, other: &EdwardsPoint) -> Self::Output {
+        self + &-&CachedPoint::from(other)
     }
 }
 
@@ -264,7 +739,7 @@ impl<'a, 'b> Mul<&'b EdwardsPoint> for &'a Scalar {
             EdwardsPoint::conditional_swap(&mut p, &mut q, b);
 
             q = &q + &p;
-            p = &p + &p;
+            p = p.double();
 
             EdwardsPoint::conditional_swap(&mut p, &mut q, b);
         }
@@ -273,6 +748,14 @@ impl<'a, 'b> Mul<&'b EdwardsPoint> for &'a Scalar {
     }
 }
 
+impl Mul<&Scalar> for &EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    fn mul(self, scalar: &Scalar) -> EdwardsPoint {
+        scalar * self
+    }
+}
+
 impl ConditionallySelectable for EdwardsPoint {
     fn conditional_select(p: &Self, q: &Self, choice: Choice) -> Self {
         let mut selection = Self::default();
@@ -328,8 +811,11 @@ impl PartialEq for EdwardsPoint {
 #[cfg(test)]
 mod tests {
 
-    use super::EdwardsPoint;
-    use crate::Scalar;
+    use super::{CompressedY, EdwardsPoint, SpacedNafTables};
+    use crate::{
+        field::{FieldElement, FieldImplementation},
+        Scalar,
+    };
 
     #[test]
     fn test_neutral_is_neutral() {
@@ -342,6 +828,15 @@ mod tests {
         assert_eq!(ne, &s * &ne);
     }
 
+    #[test]
+    fn default_is_not_a_point() {
+        let zero = EdwardsPoint::default();
+        assert!(!zero.is_identity());
+        assert!(!zero.is_small_order());
+        assert!(!zero.is_torsion_free());
+        assert!(EdwardsPoint::neutral_element().is_identity());
+    }
+
     #[test]
     fn test_addition_vs_multiplication() {
         let p = EdwardsPoint::basepoint();
@@ -394,4 +889,268 @@ mod tests {
             &scalar * &montgomery_basepoint
         );
     }
+
+    #[test]
+    fn mul_base() {
+        let bp = EdwardsPoint::basepoint();
+
+        let mut ell_minus_one = Scalar(Scalar::ell());
+        ell_minus_one.0[0] -= 1;
+        let scalars = [
+            Scalar::default(),
+            Scalar::one(),
+            Scalar::from(8),
+            ell_minus_one,
+            // not reduced
+            Scalar([0xff; 32]),
+            Scalar::from_u512_le(&[0x5a; 64]),
+        ];
+
+        for scalar in scalars.iter() {
+            assert_eq!(EdwardsPoint::mul_base(scalar), scalar * &bp);
+        }
+    }
+
+    #[test]
+    fn vartime_double_scalar_mul_basepoint() {
+        let bp = EdwardsPoint::basepoint();
+        let point = &Scalar::from(1234567) * &bp;
+
+        let scalars = [
+            Scalar::default(),
+            Scalar::one(),
+            Scalar::from_u512_le(&[0x5a; 64]),
+            Scalar::from_u512_le(&[0xa5; 64]),
+        ];
+
+        for a in scalars.iter() {
+            for b in scalars.iter() {
+                assert_eq!(
+                    EdwardsPoint::vartime_double_scalar_mul_basepoint(a, &point, b),
+                    &(a * &point) + &(b * &bp)
+                );
+            }
+        }
+
+        // `b` need not be reduced
+        let b = Scalar([0xff; 32]);
+        assert_eq!(
+            EdwardsPoint::vartime_double_scalar_mul_basepoint(&scalars[2], &point, &b),
+            &(&scalars[2] * &point) + &(&b * &bp)
+        );
+    }
+
+    fn spaced_naf_tables_with<const N: usize>() {
+        let bp = EdwardsPoint::basepoint();
+        // with a component of small order, which must be kept exactly
+        let order_two = CompressedY([
+            0xec, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0x7f,
+        ])
+        .decompressed()
+        .unwrap();
+        let point = &(&Scalar::from(1234567) * &bp) + &order_two;
+        let tables = SpacedNafTables::<N>::new(&point);
+
+        let scalars = [
+            Scalar::default(),
+            Scalar::one(),
+            Scalar::from_u512_le(&[0x5a; 64]),
+            Scalar::from_u512_le(&[0xa5; 64]),
+        ];
+        for a in scalars.iter() {
+            for b in scalars.iter().chain([Scalar([0xff; 32])].iter()) {
+                assert_eq!(
+                    tables.vartime_double_scalar_mul_basepoint(a, b),
+                    EdwardsPoint::vartime_double_scalar_mul_basepoint(a, &point, b),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn spaced_naf_tables() {
+        spaced_naf_tables_with::<1>();
+        spaced_naf_tables_with::<2>();
+        spaced_naf_tables_with::<4>();
+        spaced_naf_tables_with::<8>();
+    }
+
+    #[test]
+    fn doubling() {
+        let bp = EdwardsPoint::basepoint();
+        let points = [
+            EdwardsPoint::neutral_element(),
+            bp,
+            &Scalar::from_u512_le(&[0x5a; 64]) * &bp,
+            // the point of order two
+            EdwardsPoint([
+                FieldElement::ZERO,
+                -&FieldElement::ONE,
+                FieldElement::ONE,
+                FieldElement::ZERO,
+            ]),
+        ];
+        for point in points.iter() {
+            assert_eq!(point.double(), point + point);
+        }
+    }
+
+    #[test]
+    fn group_api() {
+        use subtle::{Choice, ConditionallyNegatable};
+
+        let bp = EdwardsPoint::basepoint();
+        let p = &Scalar::from(1234567) * &bp;
+        let q = &p * &Scalar::from(89);
+
+        assert_eq!(&(&p + &q) - &q, p);
+        assert!((&p - &p).is_identity());
+        assert!(!p.is_identity());
+        assert_eq!(p.double(), &Scalar::from(2) * &p);
+
+        let mut r = p;
+        r.conditional_negate(Choice::from(0));
+        assert_eq!(r, p);
+        r.conditional_negate(Choice::from(1));
+        assert_eq!(r, -&p);
+
+        // the point of order two
+        let t = EdwardsPoint([
+            FieldElement::ZERO,
+            -&FieldElement::ONE,
+            FieldElement::ONE,
+            FieldElement::ZERO,
+        ]);
+        assert!(t.is_small_order());
+        assert!(!t.is_torsion_free());
+        assert!(t.mul_by_cofactor().is_identity());
+
+        let p_plus_t = &p + &t;
+        assert!(p.is_torsion_free());
+        assert!(!p.is_small_order());
+        assert!(!p_plus_t.is_torsion_free());
+        assert!(!p_plus_t.is_small_order());
+        assert_eq!(p_plus_t.mul_by_cofactor(), p.mul_by_cofactor());
+        assert!(EdwardsPoint::neutral_element().is_torsion_free());
+
+        for point in [p, t, p_plus_t, EdwardsPoint::neutral_element()] {
+            assert_eq!(point.vartime_is_torsion_free(), point.is_torsion_free());
+        }
+        assert!(!EdwardsPoint::default().vartime_is_torsion_free());
+    }
+
+    #[test]
+    fn canonical_decompression() {
+        use super::CompressedY;
+        use crate::Error;
+
+        let bp = EdwardsPoint::basepoint();
+        for point in [bp, -&bp, EdwardsPoint::neutral_element()] {
+            assert_eq!(point.compressed().decompressed_canonical(), Ok(point));
+        }
+
+        // y = p, i.e., zero
+        let mut y_is_p = [0xff; 32];
+        y_is_p[0] = 0xed;
+        y_is_p[31] = 0x7f;
+        assert_eq!(
+            CompressedY(y_is_p).decompressed_canonical(),
+            Err(Error::NonCanonicalFieldElement)
+        );
+        assert!(CompressedY(y_is_p).decompressed().is_ok());
+
+        // y = p + 1, i.e., the neutral element
+        let mut y_is_p_plus_one = y_is_p;
+        y_is_p_plus_one[0] = 0xee;
+        assert_eq!(
+            CompressedY(y_is_p_plus_one).decompressed_canonical(),
+            Err(Error::NonCanonicalFieldElement)
+        );
+        assert_eq!(
+            CompressedY(y_is_p_plus_one).decompressed(),
+            Ok(EdwardsPoint::neutral_element())
+        );
+
+        // y = 2 is not on the curve
+        let mut y_is_two = [0u8; 32];
+        y_is_two[0] = 2;
+        assert_eq!(
+            CompressedY(y_is_two).decompressed_canonical(),
+            Err(Error::PointNotOnCurve)
+        );
+
+        // the neutral element with the sign bit set
+        let mut negative_zero = EdwardsPoint::neutral_element().compressed().0;
+        negative_zero[31] |= 0x80;
+        assert_eq!(
+            CompressedY(negative_zero).decompressed_canonical(),
+            Err(Error::NegativeZero)
+        );
+        assert_eq!(
+            CompressedY(negative_zero).decompressed(),
+            Ok(EdwardsPoint::neutral_element())
+        );
+    }
+
+    #[test]
+    fn constant_time_decompression() {
+        use super::CompressedY;
+        use crate::hash::Sha512;
+
+        let check = |bytes: [u8; 32]| {
+            let compressed = CompressedY(bytes);
+            let expected = compressed.decompressed().ok();
+            let ct: Option<EdwardsPoint> = compressed.decompressed_ct().into();
+            assert_eq!(ct, expected);
+            if let (Some(ct), Some(expected)) = (ct, expected) {
+                // also the same coordinates, not just the same point
+                assert_eq!(ct.0, expected.0);
+            }
+        };
+
+        // pseudorandom encodings, about half of which are on the curve
+        let mut on_curve = 0;
+        for i in 0u32..256 {
+            let hash = Sha512::new().updated(&i.to_le_bytes()).finalize();
+            let bytes: [u8; 32] = hash[..32].try_into().unwrap();
+            on_curve += CompressedY(bytes).decompressed().is_ok() as u32;
+            check(bytes);
+        }
+        assert!(on_curve > 64 && on_curve < 192);
+
+        // small y, and y close to p and 2^255, with both signs;
+        // this includes the points of order one, two and four
+        let mut p = [0xffu8; 32];
+        p[0] = 0xed;
+        p[31] = 0x7f;
+        for offset in 0u8..19 {
+            for sign in [0, 0x80] {
+                let mut bytes = [0u8; 32];
+                bytes[0] = offset;
+                bytes[31] |= sign;
+                check(bytes);
+
+                // p - offset and p + offset
+                for byte in [p[0] - offset, p[0] + offset] {
+                    let mut bytes = p;
+                    bytes[0] = byte;
+                    bytes[31] |= sign;
+                    check(bytes);
+                }
+
+                let mut bytes = [0xff; 32];
+                bytes[0] -= offset;
+                bytes[31] = 0x7f | sign;
+                check(bytes);
+            }
+        }
+
+        // the basepoint and some multiples
+        let bp = EdwardsPoint::basepoint();
+        for point in [bp, -&bp, bp.double(), EdwardsPoint::neutral_element()] {
+            check(point.compressed().0);
+        }
+    }
 }
@@ -0,0 +1,332 @@
+//! Multiscalar multiplication, computing `scalars[0] * points[0] + ... + scalars[n-1] * points[n-1]`.
+//!
+//! The constant-time variant uses Straus' method with signed radix 16 digits and
+//! constant-time table lookups. The variable-time variant uses Straus' method with
+//! width-5 NAFs for up to `PIPPENGER_THRESHOLD` terms, and Pippenger's bucket method
+//! for more terms.
+//!
+//! Both work without allocation: the caller provides one `MultiscalarScratch` per term.
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroize;
+
+use super::{CachedPoint, EdwardsPoint};
+use crate::{field::FieldElement, scalar::Scalar, Error, Result};
+
+/// from this number of terms on, `vartime_multiscalar_mul` uses Pippenger's method
+pub const PIPPENGER_THRESHOLD: usize = 190;
+
+/// Scratch space for one term of a multiscalar multiplication,
+/// holding its scalar's digits and a table of multiples of its point.
+///
+/// This is large (nine points and 288 bytes), so in constrained environments,
+/// consider reusing scratch space between multiplications.
+#[derive(Clone, Debug)]
+pub struct MultiscalarScratch {
+    digits: [i8; 256],
+    table: [CachedPoint; 8],
+    // a bucket of Pippenger's method, or the point of a kept term
+    bucket: EdwardsPoint,
+    scalar: Scalar,
+}
+
+impl Default for MultiscalarScratch {
+    fn default() -> Self {
+        MultiscalarScratch {
+            digits: [0; 256],
+            table: [CachedPoint::default(); 8],
+            bucket: EdwardsPoint::neutral_element(),
+            scalar: Scalar::default(),
+        }
+    }
+}
+
+impl Zeroize for MultiscalarScratch {
+    fn zeroize(&mut self) {
+        self.digits.zeroize();
+        self.table.zeroize();
+        self.bucket.0.zeroize();
+        self.scalar.zeroize();
+    }
+}
+
+fn check_inputs(
+    scalars: &[Scalar],
+    points: &[EdwardsPoint],
+    scratch: &[MultiscalarScratch],
+) -> Result {
+    if scalars.len() != points.len() {
+        return Err(Error::MultiscalarLengthMismatch);
+    }
+    if scratch.len() < points.len() {
+        return Err(Error::ScratchTooSmall);
+    }
+    for scalar in scalars {
+        if !bool::from(scalar.0.ct_eq(&scalar.reduce().0)) {
+            return Err(Error::NonCanonicalScalar);
+        }
+    }
+    Ok(())
+}
+
+impl EdwardsPoint {
+    /// Compute `scalars[0] * points[0] + ... + scalars[n-1] * points[n-1]` in constant time.
+    ///
+    /// The scalars must be reduced, and `scratch` must have at least as many
+    /// entries as there are points; it is zeroized afterwards.
+    pub fn multiscalar_mul(
+        scalars: &[Scalar],
+        points: &[EdwardsPoint],
+        scratch: &mut [MultiscalarScratch],
+    ) -> Result<EdwardsPoint> {
+        check_inputs(scalars, points, scratch)?;
+        let scratch = &mut scratch[..points.len()];
+
+        for ((scalar, point), scratch) in scalars.iter().zip(points).zip(scratch.iter_mut()) {
+            scratch.digits[..64].copy_from_slice(&scalar.as_radix_16());
+
+            // the multiples `P, 2P, ..., 8P`
+            let cached = CachedPoint::from(point);
+            let mut multiple = *point;
+            scratch.table[0] = cached;
+            for entry in scratch.table.iter_mut().skip(1) {
+                multiple = &multiple + &cached;
+                *entry = CachedPoint::from(&multiple);
+            }
+        }
+
+        let mut sum = EdwardsPoint::neutral_element();
+        for i in (0..64).rev() {
+            if i != 63 {
+                for _ in 0..4 {
+                    sum = sum.double();
+                }
+            }
+            for scratch in scratch.iter() {
+                sum = &sum + &CachedPoint::select(&scratch.table, scratch.digits[i]);
+            }
+        }
+
+        scratch.iter_mut().for_each(Zeroize::zeroize);
+        Ok(sum)
+    }
+
+    /// Compute `scalars[0] * points[0] + ... + scalars[n-1] * points[n-1]`.
+    ///
+    /// The scalars must be reduced, and `scratch` must have at least as many
+    /// entries as there are points.
+    ///
+    /// This is *not* constant-time, only use it on public inputs.
+    pub fn vartime_multiscalar_mul(
+        scalars: &[Scalar],
+        points: &[EdwardsPoint],
+        scratch: &mut [MultiscalarScratch],
+    ) -> Result<EdwardsPoint> {
+        check_inputs(scalars, points, scratch)?;
+        let scratch = &mut scratch[..points.len()];
+
+        for ((scalar, point), scratch) in scalars.iter().zip(points).zip(scratch.iter_mut()) {
+            scratch.vartime_prepare(scalar, point, points.len());
+        }
+        Ok(MultiscalarScratch::vartime_sum(scratch))
+    }
+}
+
+/// the window width of Pippenger's method, such that there are
+/// enough scratch entries for the `2^(w-1)` buckets
+fn pippenger_width(terms: usize) -> usize {
+    if terms < 500 {
+        6
+    } else {
+        7
+    }
+}
+
+impl MultiscalarScratch {
+    /// Keep a term that is computed in several passes, until `vartime_prepare`.
+    pub(crate) fn keep(&mut self, scalar: &Scalar, point: &EdwardsPoint) {
+        self.scalar = scalar.clone();
+        self.bucket = *point;
+    }
+
+    /// The term last passed to `keep`.
+    pub(crate) fn kept(&self) -> (Scalar, EdwardsPoint) {
+        (self.scalar.clone(), self.bucket)
+    }
+
+    /// Prepare the term `scalar * point` of a variable-time multiscalar
+    /// multiplication with `terms` terms, for `vartime_sum`.
+    ///
+    /// The scalar must be reduced.
+    pub(crate) fn vartime_prepare(&mut self, scalar: &Scalar, point: &EdwardsPoint, terms: usize) {
+        if terms < PIPPENGER_THRESHOLD {
+            self.digits = scalar.non_adjacent_form(5);
+
+            // the odd multiples `P, 3P, ..., 15P`
+            let double = CachedPoint::from(&point.double());
+            let mut multiple = *point;
+            self.table[0] = CachedPoint::from(point);
+            for entry in self.table.iter_mut().skip(1) {
+                multiple = &multiple + &double;
+                *entry = CachedPoint::from(&multiple);
+            }
+        } else {
+            self.digits = scalar.as_radix_2w(pippenger_width(terms));
+            self.table[0] = CachedPoint::from(point);
+        }
+    }
+
+    /// Sum the terms prepared by `vartime_prepare`, one per scratch entry.
+    ///
+    /// This is *not* constant-time, only use it on public inputs.
+    pub(crate) fn vartime_sum(scratch: &mut [MultiscalarScratch]) -> EdwardsPoint {
+        if scratch.len() < PIPPENGER_THRESHOLD {
+            vartime_straus(scratch)
+        } else {
+            vartime_pippenger(scratch)
+        }
+    }
+}
+
+fn vartime_straus(scratch: &[MultiscalarScratch]) -> EdwardsPoint {
+    let mut sum = EdwardsPoint::neutral_element();
+    for i in (0..256).rev() {
+        sum = sum.double();
+        for scratch in scratch.iter() {
+            let digit = scratch.digits[i];
+            if digit > 0 {
+                sum = &sum + &scratch.table[digit as usize / 2];
+            } else if digit < 0 {
+                sum = &sum + &-&scratch.table[(-digit) as usize / 2];
+            }
+        }
+    }
+    sum
+}
+
+fn vartime_pippenger(scratch: &mut [MultiscalarScratch]) -> EdwardsPoint {
+    let w = pippenger_width(scratch.len());
+    let buckets = 1 << (w - 1);
+    let windows = 256usize.div_ceil(w);
+
+    let mut sum = EdwardsPoint::neutral_element();
+    for window in (0..windows).rev() {
+        for _ in 0..w {
+            sum = sum.double();
+        }
+
+        // sort the points into buckets by their digit
+        for scratch in scratch[..buckets].iter_mut() {
+            scratch.bucket = EdwardsPoint::neutral_element();
+        }
+        for i in 0..scratch.len() {
+            let digit = scratch[i].digits[window];
+            if digit > 0 {
+                let b = digit as usize - 1;
+                scratch[b].bucket = &scratch[b].bucket + &scratch[i].table[0];
+            } else if digit < 0 {
+                let b = (-digit) as usize - 1;
+                scratch[b].bucket = &scratch[b].bucket + &-&scratch[i].table[0];
+            }
+        }
+
+        // `sum(b * bucket[b - 1])`, via running sums of the buckets
+        let mut running_sum = EdwardsPoint::neutral_element();
+        let mut window_sum = EdwardsPoint::neutral_element();
+        for scratch in scratch[..buckets].iter().rev() {
+            running_sum = &running_sum + &scratch.bucket;
+            window_sum = &window_sum + &running_sum;
+        }
+
+        sum = &sum + &window_sum;
+    }
+    sum
+}
+
+impl ConditionallySelectable for CachedPoint {
+    fn conditional_select(p: &Self, q: &Self, choice: Choice) -> Self {
+        CachedPoint {
+            y_plus_x: FieldElement::conditional_select(&p.y_plus_x, &q.y_plus_x, choice),
+            y_minus_x: FieldElement::conditional_select(&p.y_minus_x, &q.y_minus_x, choice),
+            z: FieldElement::conditional_select(&p.z, &q.z, choice),
+            t2d: FieldElement::conditional_select(&p.t2d, &q.t2d, choice),
+        }
+    }
+}
+
+impl CachedPoint {
+    /// Compute `digit * P` in constant time, for `-8 <= digit <= 8`,
+    /// from the table of multiples `P, 2P, ..., 8P`.
+    fn select(table: &[CachedPoint; 8], digit: i8) -> CachedPoint {
+        let negative = (digit as u8) >> 7;
+        let absolute = (digit - ((-(negative as i8)) & digit) * 2) as u8;
+
+        let mut point = CachedPoint::from(&EdwardsPoint::neutral_element());
+        for (i, entry) in table.iter().enumerate() {
+            point.conditional_assign(entry, absolute.ct_eq(&(i as u8 + 1)));
+        }
+
+        let minus_point = -&point;
+        point.conditional_assign(&minus_point, Choice::from(negative));
+        point
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiscalar_mul() {
+        const N: usize = 5;
+        let mut scalars: [Scalar; N] = core::array::from_fn(|i| Scalar::from(i as u64 * 1000));
+        scalars[4] = Scalar::from_u512_le(&[0x5a; 64]);
+        let points: [EdwardsPoint; N] =
+            core::array::from_fn(|i| &Scalar::from(i as u64 + 7) * &EdwardsPoint::basepoint());
+        let mut scratch: [MultiscalarScratch; N] = Default::default();
+
+        let mut expected = EdwardsPoint::neutral_element();
+        for (scalar, point) in scalars.iter().zip(points.iter()) {
+            expected = &expected + &(scalar * point);
+        }
+
+        assert_eq!(
+            EdwardsPoint::multiscalar_mul(&scalars, &points, &mut scratch),
+            Ok(expected)
+        );
+        assert_eq!(
+            EdwardsPoint::vartime_multiscalar_mul(&scalars, &points, &mut scratch),
+            Ok(expected)
+        );
+
+        for n in 0..N {
+            assert_eq!(
+                EdwardsPoint::multiscalar_mul(&scalars[..n], &points[..n], &mut scratch),
+                EdwardsPoint::vartime_multiscalar_mul(&scalars[..n], &points[..n], &mut scratch),
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_inputs() {
+        let scalars = [Scalar::one(), Scalar::one()];
+        let points = [EdwardsPoint::basepoint(); 2];
+        let mut scratch = [MultiscalarScratch::default(), MultiscalarScratch::default()];
+
+        assert_eq!(
+            EdwardsPoint::vartime_multiscalar_mul(&scalars[..1], &points, &mut scratch),
+            Err(Error::MultiscalarLengthMismatch)
+        );
+        assert_eq!(
+            EdwardsPoint::multiscalar_mul(&scalars, &points, &mut scratch[..1]),
+            Err(Error::ScratchTooSmall)
+        );
+        assert_eq!(
+            EdwardsPoint::multiscalar_mul(
+                &[Scalar::one(), Scalar(Scalar::ell())],
+                &points,
+                &mut scratch
+            ),
+            Err(Error::NonCanonicalScalar)
+        );
+    }
+}
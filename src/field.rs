@@ -4,7 +4,7 @@ use core::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use subtle::{ConditionallySelectable, ConstantTimeEq};
+use subtle::{Choice, ConditionallyNegatable, ConditionallySelectable, ConstantTimeEq};
 
 use crate::{Error, Result};
 
@@ -119,6 +119,34 @@ where
     fn pow2523(&self) -> Self;
 }
 
+/// Compute the non-negative square root of `u/v`, or of `i * u/v` if `u/v` is not a square,
+/// in constant time, as `SQRT_RATIO_M1` in [RFC 9496, section 4.2][rfc].
+///
+/// The returned choice is set if `u/v` is a square (including the case `u = 0`);
+/// for `v = 0` and `u != 0`, it is not set and the root returned is zero.
+///
+/// [rfc]: https://www.rfc-editor.org/rfc/rfc9496#section-4.2
+pub(crate) fn sqrt_ratio_i(u: &FieldElement, v: &FieldElement) -> (Choice, FieldElement) {
+    let v3 = &v.squared() * v;
+    let v7 = &v3.squared() * v;
+    let mut r = &(u * &v3) * &(u * &v7).pow2523();
+    let check = v * &r.squared();
+
+    let minus_u = -u;
+    let correct_sign_sqrt = check.ct_eq(u);
+    let flipped_sign_sqrt = check.ct_eq(&minus_u);
+    let flipped_sign_sqrt_i = check.ct_eq(&(&minus_u * &FieldElement::I));
+
+    let r_prime = &r * &FieldElement::I;
+    r.conditional_assign(&r_prime, flipped_sign_sqrt | flipped_sign_sqrt_i);
+
+    // choose the non-negative square root
+    let r_is_negative = Choice::from(r.parity());
+    r.conditional_negate(r_is_negative);
+
+    (correct_sign_sqrt | flipped_sign_sqrt, r)
+}
+
 #[cfg(tweetnacl)]
 pub mod tweetnacl;
 #[cfg(tweetnacl)]
@@ -1,5 +1,7 @@
 use core::num::Wrapping;
 
+use zeroize::Zeroize;
+
 use crate::constants::SHA512_LENGTH;
 
 pub type Digest = [u8; SHA512_LENGTH];
@@ -297,3 +299,68 @@ impl Sha512 {
         self.digest
     }
 }
+
+/// HMAC-SHA512 (RFC 2104), on top of our `Sha512`
+pub(crate) struct HmacSha512 {
+    inner: Sha512,
+    outer: Sha512,
+}
+
+impl HmacSha512 {
+    pub fn new(key: &[u8]) -> HmacSha512 {
+        // keys longer than the block size are hashed first
+        let mut padded_key = [0u8; 128];
+        if key.len() > 128 {
+            padded_key[..SHA512_LENGTH].copy_from_slice(&Sha512::new().updated(key).finalize());
+        } else {
+            padded_key[..key.len()].copy_from_slice(key);
+        }
+
+        let mut pad = [0u8; 128];
+        for (p, k) in pad.iter_mut().zip(padded_key.iter()) {
+            *p = k ^ 0x36;
+        }
+        let inner = Sha512::new().updated(&pad);
+        for (p, k) in pad.iter_mut().zip(padded_key.iter()) {
+            *p = k ^ 0x5c;
+        }
+        let outer = Sha512::new().updated(&pad);
+
+        pad.zeroize();
+        padded_key.zeroize();
+
+        HmacSha512 { inner, outer }
+    }
+
+    pub fn updated(mut self, data: &[u8]) -> Self {
+        self.inner.update(data);
+        self
+    }
+
+    pub fn finalize(self) -> Digest {
+        let inner = self.inner.finalize();
+        self.outer.updated(&inner).finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn hmac_sha512_rfc_4231() {
+        // test case 2
+        let mac = HmacSha512::new(b"Jefe")
+            .updated(b"what do ya want ")
+            .updated(b"for nothing?")
+            .finalize();
+        assert_eq!(mac, hex!("164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"));
+
+        // test case 6, with a key longer than the block size
+        let mac = HmacSha512::new(&[0xaa; 131])
+            .updated(b"Test Using Larger Than Block-Size Key - Hash Key First")
+            .finalize();
+        assert_eq!(mac, hex!("80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"));
+    }
+}
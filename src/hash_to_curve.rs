@@ -0,0 +1,385 @@
+//! Hashing to edwards25519 and curve25519, following [RFC 9380][rfc].
+//!
+//! This implements the suites `edwards25519_XMD:SHA-512_ELL2_RO_` and `_NU_`
+//! (`EdwardsPoint::hash_to_curve` and `EdwardsPoint::encode_to_curve`), and
+//! `curve25519_XMD:SHA-512_ELL2_RO_` and `_NU_` (the same on `MontgomeryPoint`).
+//! Messages are expanded with `expand_message_xmd` using SHA-512, mapped to the
+//! Montgomery curve with Elligator 2, and to the Edwards curve with the birational map.
+//!
+//! The "random oracle" variants `hash_to_curve` are uniformly distributed, the
+//! "nonuniform" variants `encode_to_curve` are about twice as fast, but only reach
+//! about half of the points. Both output points in the prime order subgroup.
+//!
+//! Applications must choose a domain separation tag (DST) following [section 3.1][dst],
+//! which usually includes the suite ID, e.g. `b"MYAPP-V1-CS01-with-edwards25519_XMD:SHA-512_ELL2_RO_"`.
+//!
+//! ```
+//! use salty::{hash_to_curve::EDWARDS25519_XMD_SHA512_ELL2_RO, EdwardsPoint};
+//!
+//! let dst = b"SALTY-EXAMPLE-V1-CS01-with-edwards25519_XMD:SHA-512_ELL2_RO_";
+//! assert!(dst.ends_with(EDWARDS25519_XMD_SHA512_ELL2_RO.as_bytes()));
+//!
+//! let point = EdwardsPoint::hash_to_curve(b"message", dst);
+//! assert!(point.is_torsion_free());
+//! ```
+//!
+//! [rfc]: https://www.rfc-editor.org/rfc/rfc9380
+//! [dst]: https://www.rfc-editor.org/rfc/rfc9380#section-3.1
+use subtle::{Choice, ConditionallyNegatable, ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroize;
+
+use crate::{
+    constants::SHA512_LENGTH,
+    edwards::EdwardsPoint,
+    field::{sqrt_ratio_i, FieldElement, FieldImplementation},
+    hash::Sha512,
+    montgomery::MontgomeryPoint,
+    Error, Result,
+};
+
+/// suite ID of `EdwardsPoint::hash_to_curve`
+pub const EDWARDS25519_XMD_SHA512_ELL2_RO: &str = "edwards25519_XMD:SHA-512_ELL2_RO_";
+/// suite ID of `EdwardsPoint::encode_to_curve`
+pub const EDWARDS25519_XMD_SHA512_ELL2_NU: &str = "edwards25519_XMD:SHA-512_ELL2_NU_";
+/// suite ID of `MontgomeryPoint::hash_to_curve`
+pub const CURVE25519_XMD_SHA512_ELL2_RO: &str = "curve25519_XMD:SHA-512_ELL2_RO_";
+/// suite ID of `MontgomeryPoint::encode_to_curve`
+pub const CURVE25519_XMD_SHA512_ELL2_NU: &str = "curve25519_XMD:SHA-512_ELL2_NU_";
+
+/// the maximal output length of `expand_message_xmd`
+pub const EXPAND_MESSAGE_XMD_MAX_LENGTH: usize = 255 * SHA512_LENGTH;
+
+/// the block size of SHA-512
+const SHA512_BLOCK_LENGTH: usize = 128;
+
+/// bytes hashed per field element, such that the bias of their reduction is negligible
+const HASH_TO_FIELD_LENGTH: usize = 48;
+
+/// `J = 486662`, the coefficient of the Montgomery curve `v^2 = u^3 + J u^2 + u`
+const J: [u8; 32] = [
+    0x06, 0x6d, 0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+/// the non-negative `sqrt(-486664)`, used in the birational map
+const SQRT_MINUS_J_MINUS_TWO: [u8; 32] = [
+    0x06, 0x7e, 0x45, 0xff, 0xaa, 0x04, 0x6e, 0xcc, 0x82, 0x1a, 0x7d, 0x4b, 0xd1, 0xd3, 0xa1, 0xc5,
+    0x7e, 0x4f, 0xfc, 0x03, 0xdc, 0x08, 0x7b, 0xd2, 0xbb, 0x06, 0xa0, 0x60, 0xf4, 0xed, 0x26, 0x0f,
+];
+
+/// Expand a message and domain separation tag to `output.len()` pseudorandom bytes
+/// with SHA-512, following [RFC 9380, section 5.3.1][xmd].
+///
+/// Tags longer than 255 bytes are hashed first, as in [section 5.3.3][oversize].
+/// Returns `Error::ExpandMessageTooLong` if more than `EXPAND_MESSAGE_XMD_MAX_LENGTH`
+/// bytes are requested.
+///
+/// [xmd]: https://www.rfc-editor.org/rfc/rfc9380#section-5.3.1
+/// [oversize]: https://www.rfc-editor.org/rfc/rfc9380#section-5.3.3
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], output: &mut [u8]) -> Result {
+    if output.len() > EXPAND_MESSAGE_XMD_MAX_LENGTH {
+        return Err(Error::ExpandMessageTooLong);
+    }
+
+    let oversize_dst;
+    let dst = if dst.len() > 255 {
+        oversize_dst = Sha512::new()
+            .updated(b"H2C-OVERSIZE-DST-")
+            .updated(dst)
+            .finalize();
+        &oversize_dst[..]
+    } else {
+        dst
+    };
+    // DST_prime = DST || I2OSP(len(DST), 1)
+    let dst_length = [dst.len() as u8];
+
+    let mut b0 = Sha512::new()
+        .updated(&[0u8; SHA512_BLOCK_LENGTH])
+        .updated(msg)
+        .updated(&(output.len() as u16).to_be_bytes())
+        .updated(&[0])
+        .updated(dst)
+        .updated(&dst_length)
+        .finalize();
+
+    let mut bi = [0u8; SHA512_LENGTH];
+    for (i, chunk) in output.chunks_mut(SHA512_LENGTH).enumerate() {
+        // b_i = H(strxor(b_0, b_(i - 1)) || I2OSP(i, 1) || DST_prime),
+        // starting from b_1 = H(b_0 || I2OSP(1, 1) || DST_prime)
+        for (b, b0) in bi.iter_mut().zip(b0.iter()) {
+            *b ^= b0;
+        }
+        bi = Sha512::new()
+            .updated(&bi)
+            .updated(&[i as u8 + 1])
+            .updated(dst)
+            .updated(&dst_length)
+            .finalize();
+        chunk.copy_from_slice(&bi[..chunk.len()]);
+    }
+
+    b0.zeroize();
+    bi.zeroize();
+    Ok(())
+}
+
+/// Hash to `N` field elements, following [RFC 9380, section 5.2][rfc], for `N <= 2`.
+///
+/// [rfc]: https://www.rfc-editor.org/rfc/rfc9380#section-5.2
+fn hash_to_field<const N: usize>(msg: &[u8], dst: &[u8]) -> [FieldElement; N] {
+    let mut uniform_bytes = [0u8; 2 * HASH_TO_FIELD_LENGTH];
+    let uniform_bytes = &mut uniform_bytes[..N * HASH_TO_FIELD_LENGTH];
+    // can't fail, as at most 96 bytes are requested
+    expand_message_xmd(msg, dst, uniform_bytes).ok();
+
+    let elements = core::array::from_fn(|i| {
+        let mut bytes = [0u8; HASH_TO_FIELD_LENGTH];
+        bytes.copy_from_slice(&uniform_bytes[i * HASH_TO_FIELD_LENGTH..][..HASH_TO_FIELD_LENGTH]);
+        let element = from_be_bytes_wide(&bytes);
+        bytes.zeroize();
+        element
+    });
+
+    uniform_bytes.zeroize();
+    elements
+}
+
+/// Reduce a 384 bit big-endian integer modulo `p = 2^255 - 19`.
+fn from_be_bytes_wide(bytes: &[u8; HASH_TO_FIELD_LENGTH]) -> FieldElement {
+    let mut le_bytes = *bytes;
+    le_bytes.reverse();
+
+    // split into `low + 2^255 * top + 2^256 * high`, where `2^255 = 19` and `2^256 = 38`
+    let mut low = [0u8; 32];
+    low.copy_from_slice(&le_bytes[..32]);
+    let top = low[31] >> 7;
+    low[31] &= 0x7f;
+    let mut high = [0u8; 32];
+    high[..16].copy_from_slice(&le_bytes[32..]);
+
+    let mut nineteen_top = [0u8; 32];
+    nineteen_top[0] = 19 * top;
+    let mut thirty_eight = [0u8; 32];
+    thirty_eight[0] = 38;
+
+    let element = &(&FieldElement::from_bytes_unchecked(&low)
+        + &FieldElement::from_bytes_unchecked(&nineteen_top))
+        + &(&FieldElement::from_bytes_unchecked(&high)
+            * &FieldElement::from_bytes_unchecked(&thirty_eight));
+
+    le_bytes.zeroize();
+    low.zeroize();
+    high.zeroize();
+    element
+}
+
+/// Elligator 2 for curve25519, following [RFC 9380, section 6.7.1][rfc], with `Z = 2`.
+/// Returns the coordinates `(s, t)` of a point on the Montgomery curve, in constant time.
+///
+/// [rfc]: https://www.rfc-editor.org/rfc/rfc9380#section-6.7.1
+fn elligator2(u: &FieldElement) -> (FieldElement, FieldElement) {
+    let one = FieldElement::ONE;
+    let j = FieldElement::from_bytes_unchecked(&J);
+    let minus_j = -&j;
+
+    // x1 = -J / (1 + Z * u^2), or -J if the denominator vanishes
+    let u_squared = u.squared();
+    let mut x1 = &minus_j * &(&(&u_squared + &u_squared) + &one).inverse();
+    let x1_is_zero = x1.ct_eq(&FieldElement::ZERO);
+    x1.conditional_assign(&minus_j, x1_is_zero);
+    // gx1 = x1^3 + J * x1^2 + x1
+    let gx1 = &x1 * &(&(&x1 * &(&x1 + &j)) + &one);
+
+    let x2 = &(-&x1) - &j;
+    let gx2 = &x2 * &(&(&x2 * &(&x2 + &j)) + &one);
+
+    // the roots are non-negative, i.e. sgn0 = 0
+    let (gx1_is_square, mut y1) = sqrt_ratio_i(&gx1, &one);
+    let (_, y2) = sqrt_ratio_i(&gx2, &one);
+    y1.conditional_negate(Choice::from(1));
+
+    let x = FieldElement::conditional_select(&x2, &x1, gx1_is_square);
+    let y = FieldElement::conditional_select(&y2, &y1, gx1_is_square);
+    (x, y)
+}
+
+/// The birational map from the Montgomery curve to the Edwards curve,
+/// following [RFC 9380, appendix D.1][rfc], in constant time.
+///
+/// `(s, t) = (u, v)` is mapped to `(x, y) = (sqrt(-486664) * s / t, (s - 1) / (s + 1))`.
+/// The exceptional points, with `t = 0` or `s = -1`, are mapped to the neutral element.
+///
+/// [rfc]: https://www.rfc-editor.org/rfc/rfc9380#appendix-D.1
+fn montgomery_to_edwards(s: &FieldElement, t: &FieldElement) -> EdwardsPoint {
+    let one = FieldElement::ONE;
+
+    let x_numerator = &FieldElement::from_bytes_unchecked(&SQRT_MINUS_J_MINUS_TWO) * s;
+    let x_denominator = t;
+    let y_numerator = s - &one;
+    let y_denominator = s + &one;
+
+    let point = EdwardsPoint([
+        &x_numerator * &y_denominator,
+        &y_numerator * x_denominator,
+        x_denominator * &y_denominator,
+        &x_numerator * &y_numerator,
+    ]);
+
+    let is_exceptional = point.0[2].ct_eq(&FieldElement::ZERO);
+    EdwardsPoint::conditional_select(&point, &EdwardsPoint::neutral_element(), is_exceptional)
+}
+
+fn map_to_curve(u: &FieldElement) -> EdwardsPoint {
+    let (s, t) = elligator2(u);
+    montgomery_to_edwards(&s, &t)
+}
+
+impl EdwardsPoint {
+    /// Hash to the prime order subgroup, with the suite `edwards25519_XMD:SHA-512_ELL2_RO_`
+    /// of [RFC 9380][rfc]: the output is indistinguishable from a random point.
+    ///
+    /// [rfc]: https://www.rfc-editor.org/rfc/rfc9380#section-3
+    pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> EdwardsPoint {
+        let [u0, u1] = hash_to_field::<2>(msg, dst);
+        (&map_to_curve(&u0) + &map_to_curve(&u1)).mul_by_cofactor()
+    }
+
+    /// Encode to the prime order subgroup, with the suite `edwards25519_XMD:SHA-512_ELL2_NU_`
+    /// of [RFC 9380][rfc]: cheaper than `hash_to_curve`, but the output is not uniformly distributed.
+    ///
+    /// [rfc]: https://www.rfc-editor.org/rfc/rfc9380#section-3
+    pub fn encode_to_curve(msg: &[u8], dst: &[u8]) -> EdwardsPoint {
+        let [u] = hash_to_field::<1>(msg, dst);
+        map_to_curve(&u).mul_by_cofactor()
+    }
+}
+
+impl MontgomeryPoint {
+    /// Hash to the prime order subgroup, with the suite `curve25519_XMD:SHA-512_ELL2_RO_`
+    /// of [RFC 9380][rfc]: the output is indistinguishable from a random point.
+    ///
+    /// The points are added on the isomorphic Edwards curve, as `MontgomeryPoint` only
+    /// holds the u-coordinate. In the negligible case that the result is the neutral
+    /// element, its u-coordinate is zero, as for `EdwardsPoint::to_montgomery`.
+    ///
+    /// [rfc]: https://www.rfc-editor.org/rfc/rfc9380#section-3
+    pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> MontgomeryPoint {
+        EdwardsPoint::hash_to_curve(msg, dst).to_montgomery()
+    }
+
+    /// Encode to the prime order subgroup, with the suite `curve25519_XMD:SHA-512_ELL2_NU_`
+    /// of [RFC 9380][rfc]: cheaper than `hash_to_curve`, but the output is not uniformly distributed.
+    ///
+    /// [rfc]: https://www.rfc-editor.org/rfc/rfc9380#section-3
+    pub fn encode_to_curve(msg: &[u8], dst: &[u8]) -> MontgomeryPoint {
+        EdwardsPoint::encode_to_curve(msg, dst).to_montgomery()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `hash_to_field` outputs for the `edwards25519_XMD:SHA-512_ELL2_NU_` and `_RO_`
+    /// test vectors of [RFC 9380, appendix J.5](https://www.rfc-editor.org/rfc/rfc9380#appendix-J.5),
+    /// for the messages "" and "abc"
+    const HASH_TO_FIELD: [(&[u8], &str, [&str; 2]); 2] = [
+        (
+            b"",
+            "7f3e7fb9428103ad7f52db32f9df32505d7b427d894c5093f7a0f0374a30641d",
+            [
+                "03fef4813c8cb5f98c6eef88fae174e6e7d5380de2b007799ac7ee712d203f3a",
+                "780bdddd137290c8f589dc687795aafae35f6b674668d92bf92ae793e6a60c75",
+            ],
+        ),
+        (
+            b"abc",
+            "09cfa30ad79bd59456594a0f5d3a76f6b71c6787b04de98be5cd201a556e253b",
+            [
+                "5081955c4141e4e7d02ec0e36becffaa1934df4d7a270f70679c78f9bd57c227",
+                "005bdc17a9b378b6272573a31b04361f21c371b256252ae5463119aa0b925b76",
+            ],
+        ),
+    ];
+
+    fn field_element(be_hex: &str) -> FieldElement {
+        let mut bytes: [u8; 32] = hex::decode(be_hex).unwrap().try_into().unwrap();
+        bytes.reverse();
+        FieldElement::from_bytes(&bytes).unwrap()
+    }
+
+    #[test]
+    fn hash_to_field_test_vectors() {
+        for (msg, nu, ro) in HASH_TO_FIELD {
+            let [u] =
+                hash_to_field::<1>(msg, b"QUUX-V01-CS02-with-edwards25519_XMD:SHA-512_ELL2_NU_");
+            assert_eq!(u, field_element(nu));
+
+            let [u0, u1] =
+                hash_to_field::<2>(msg, b"QUUX-V01-CS02-with-edwards25519_XMD:SHA-512_ELL2_RO_");
+            assert_eq!(u0, field_element(ro[0]));
+            assert_eq!(u1, field_element(ro[1]));
+        }
+    }
+
+    #[test]
+    fn wide_reduction() {
+        // 2^383 - 1 = 2^128 * (2^255 - 19) + 19 * 2^128 - 1
+        let expected =
+            &(&field_element("0000000000000000000000000000000000000000000000000000000000000013")
+                * &field_element(
+                    "0000000000000000000000000000000100000000000000000000000000000000",
+                ))
+                - &FieldElement::ONE;
+        let mut all_ones = [0xffu8; HASH_TO_FIELD_LENGTH];
+        all_ones[0] = 0x7f;
+        assert_eq!(from_be_bytes_wide(&all_ones), expected);
+
+        assert_eq!(
+            from_be_bytes_wide(&[0u8; HASH_TO_FIELD_LENGTH]),
+            FieldElement::ZERO
+        );
+    }
+
+    #[test]
+    fn exceptional_cases() {
+        // u = 0 gives the point (0, 0) of order two
+        let (s, t) = elligator2(&FieldElement::ZERO);
+        assert_eq!((s, t), (FieldElement::ZERO, FieldElement::ZERO));
+        assert!(montgomery_to_edwards(&s, &t).is_identity());
+
+        for u in [
+            FieldElement::ONE,
+            FieldElement::I,
+            field_element("0000000000000000000000000000000000000000000000000000000000001234"),
+        ] {
+            let (s, t) = elligator2(&u);
+            // on the Montgomery curve
+            let j = FieldElement::from_bytes_unchecked(&J);
+            assert_eq!(t.squared(), &s * &(&(&s * &(&s + &j)) + &FieldElement::ONE));
+            // and on the Edwards curve
+            let point = montgomery_to_edwards(&s, &t);
+            assert_eq!(point.compressed().decompressed().unwrap(), point);
+            assert_eq!(point.u(), s);
+        }
+    }
+
+    #[test]
+    fn expand_message_length() {
+        let mut output = [0u8; EXPAND_MESSAGE_XMD_MAX_LENGTH + 1];
+        assert_eq!(
+            expand_message_xmd(b"", b"DST", &mut output),
+            Err(Error::ExpandMessageTooLong)
+        );
+        assert_eq!(
+            expand_message_xmd(b"", b"DST", &mut output[..EXPAND_MESSAGE_XMD_MAX_LENGTH]),
+            Ok(())
+        );
+
+        // prefixes of longer outputs differ, as the length is hashed
+        let mut short = [0u8; 32];
+        expand_message_xmd(b"", b"DST", &mut short).unwrap();
+        assert_ne!(short[..], output[..32]);
+    }
+}
@@ -0,0 +1,94 @@
+//! Hierarchical deterministic key derivation, following
+//! [SLIP-0010](https://github.com/satoshilabs/slips/blob/master/slip-0010.md).
+//!
+//! For Ed25519, SLIP-0010 only defines hardened derivation: each child key
+//! is derived from the parent's secret, and there are no extended public keys.
+//! For non-hardened derivation, see the `bip32_ed25519` module.
+//!
+//! ```
+//! use salty::hd::{ExtendedSecretKey, HARDENED};
+//!
+//! let master = ExtendedSecretKey::from_seed(&[42u8; 32]);
+//! // m/44'/0'
+//! let account = master
+//!     .derive_child(44 | HARDENED)
+//!     .and_then(|key| key.derive_child(HARDENED))
+//!     .unwrap();
+//!
+//! let keypair = account.keypair();
+//! let signature = keypair.sign(b"salty");
+//! assert!(keypair.public().verify(b"salty", &signature).is_ok());
+//! ```
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+pub mod bip32_ed25519;
+
+use crate::{
+    constants::{SECRETKEY_SEED_LENGTH, SHA512_LENGTH},
+    hash::HmacSha512,
+    signature::Keypair,
+    Error, Result,
+};
+
+/// the length of a chain code
+pub const CHAIN_CODE_LENGTH: usize = 32;
+
+/// bit set in the index of hardened child keys
+pub const HARDENED: u32 = 0x8000_0000;
+
+/// a SLIP-0010 extended secret key: an Ed25519 seed, together with
+/// the chain code used to derive its children.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct ExtendedSecretKey {
+    seed: [u8; SECRETKEY_SEED_LENGTH],
+    chain_code: [u8; CHAIN_CODE_LENGTH],
+}
+
+impl ExtendedSecretKey {
+    /// Master key for the given seed, usually 16 to 64 bytes of entropy.
+    pub fn from_seed(seed: &[u8]) -> ExtendedSecretKey {
+        let digest = HmacSha512::new(b"ed25519 seed").updated(seed).finalize();
+        Self::from_digest(digest)
+    }
+
+    /// Derive the child key with given index, which must be hardened.
+    pub fn derive_child(&self, index: u32) -> Result<ExtendedSecretKey> {
+        if index & HARDENED == 0 {
+            return Err(Error::NonHardenedIndex);
+        }
+
+        let digest = HmacSha512::new(&self.chain_code)
+            .updated(&[0])
+            .updated(&self.seed)
+            .updated(&index.to_be_bytes())
+            .finalize();
+        Ok(Self::from_digest(digest))
+    }
+
+    /// The chain code.
+    pub fn chain_code(&self) -> &[u8; CHAIN_CODE_LENGTH] {
+        &self.chain_code
+    }
+
+    /// The secret key, as Ed25519 seed.
+    pub fn secret_bytes(&self) -> &[u8; SECRETKEY_SEED_LENGTH] {
+        &self.seed
+    }
+
+    /// The Ed25519 keypair of this extended key.
+    pub fn keypair(&self) -> Keypair {
+        Keypair::from(&self.seed)
+    }
+
+    fn from_digest(mut digest: [u8; SHA512_LENGTH]) -> ExtendedSecretKey {
+        let mut key = ExtendedSecretKey {
+            seed: [0u8; SECRETKEY_SEED_LENGTH],
+            chain_code: [0u8; CHAIN_CODE_LENGTH],
+        };
+        key.seed.copy_from_slice(&digest[..SECRETKEY_SEED_LENGTH]);
+        key.chain_code
+            .copy_from_slice(&digest[SECRETKEY_SEED_LENGTH..]);
+        digest.zeroize();
+        key
+    }
+}
@@ -0,0 +1,284 @@
+//! BIP32-Ed25519 key derivation, following
+//! [Khovratovich and Law](https://input-output-hk.github.io/adrestia/static/Ed25519_BIP.pdf),
+//! as used by e.g. Cardano wallets.
+//!
+//! Unlike SLIP-0010, this scheme supports non-hardened derivation: anyone knowing an
+//! extended public key can derive the public keys of its non-hardened children,
+//! which match those derived from the extended secret key.
+//!
+//! There are several ways to generate root keys from a seed or mnemonic, which differ
+//! between wallets. This module starts from the 64 byte root key and chain code
+//! that these produce, e.g. `SHA-512(seed)` and `SHA-256(0x01 || seed)` in the paper.
+//!
+//! ```
+//! use salty::hd::{bip32_ed25519::ExtendedSecretKey, HARDENED};
+//!
+//! # let root_key = salty::Sha512::new().updated(&[0u8; 32]).finalize();
+//! # let chain_code = [1u8; 32];
+//! let root = ExtendedSecretKey::from_root_key(&root_key, &chain_code).unwrap();
+//! let account = root.derive_child(HARDENED).unwrap();
+//!
+//! // a watch-only host can derive the same public keys
+//! let watch_only = account.public_key();
+//! assert_eq!(
+//!     &watch_only.derive_child(7).unwrap().public_key(),
+//!     account.derive_child(7).unwrap().keypair().public(),
+//! );
+//! ```
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{
+    constants::{
+        PUBLICKEY_SERIALIZED_LENGTH, SECRETKEY_EXPANDED_LENGTH, SECRETKEY_NONCE_LENGTH,
+        SECRETKEY_SCALAR_LENGTH, SHA512_LENGTH,
+    },
+    edwards::EdwardsPoint,
+    hash::HmacSha512,
+    scalar::Scalar,
+    signature::{Keypair, PublicKey, SecretKey},
+    Error, Result,
+};
+
+use super::{CHAIN_CODE_LENGTH, HARDENED};
+
+/// the length of a serialized extended secret key: scalar, nonce and chain code
+pub const EXTENDED_SECRETKEY_SERIALIZED_LENGTH: usize =
+    SECRETKEY_EXPANDED_LENGTH + CHAIN_CODE_LENGTH;
+
+/// the length of a serialized extended public key: public key and chain code
+pub const EXTENDED_PUBLICKEY_SERIALIZED_LENGTH: usize =
+    PUBLICKEY_SERIALIZED_LENGTH + CHAIN_CODE_LENGTH;
+
+/// a BIP32-Ed25519 extended secret key: an expanded secret key (scalar and nonce),
+/// together with the chain code used to derive its children.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct ExtendedSecretKey {
+    scalar: [u8; SECRETKEY_SCALAR_LENGTH],
+    nonce: [u8; SECRETKEY_NONCE_LENGTH],
+    chain_code: [u8; CHAIN_CODE_LENGTH],
+}
+
+/// a BIP32-Ed25519 extended public key: a public key, together with
+/// the chain code used to derive its non-hardened children.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtendedPublicKey {
+    public_key: PublicKey,
+    chain_code: [u8; CHAIN_CODE_LENGTH],
+}
+
+impl ExtendedSecretKey {
+    /// Root key from 64 bytes of key material and a chain code.
+    ///
+    /// Following the paper, key material whose third highest bit is set is
+    /// rejected, otherwise it is clamped to obtain the root scalar.
+    pub fn from_root_key(
+        key: &[u8; SECRETKEY_EXPANDED_LENGTH],
+        chain_code: &[u8; CHAIN_CODE_LENGTH],
+    ) -> Result<ExtendedSecretKey> {
+        if key[31] & 0b0010_0000 != 0 {
+            return Err(Error::InvalidExtendedSecretKey);
+        }
+
+        let mut root = ExtendedSecretKey {
+            scalar: [0u8; SECRETKEY_SCALAR_LENGTH],
+            nonce: [0u8; SECRETKEY_NONCE_LENGTH],
+            chain_code: *chain_code,
+        };
+        root.scalar.copy_from_slice(&key[..SECRETKEY_SCALAR_LENGTH]);
+        root.nonce.copy_from_slice(&key[SECRETKEY_SCALAR_LENGTH..]);
+
+        root.scalar[0] &= 248;
+        root.scalar[31] &= 127;
+        root.scalar[31] |= 64;
+
+        Ok(root)
+    }
+
+    /// Deserialize from scalar, nonce and chain code.
+    ///
+    /// The scalar must be a multiple of the cofactor, with its highest bit cleared.
+    pub fn from_bytes(
+        bytes: &[u8; EXTENDED_SECRETKEY_SERIALIZED_LENGTH],
+    ) -> Result<ExtendedSecretKey> {
+        if bytes[0] & 0b111 != 0 || bytes[31] & 0b1000_0000 != 0 {
+            return Err(Error::InvalidExtendedSecretKey);
+        }
+
+        let mut key = ExtendedSecretKey {
+            scalar: [0u8; SECRETKEY_SCALAR_LENGTH],
+            nonce: [0u8; SECRETKEY_NONCE_LENGTH],
+            chain_code: [0u8; CHAIN_CODE_LENGTH],
+        };
+        key.scalar.copy_from_slice(&bytes[..32]);
+        key.nonce.copy_from_slice(&bytes[32..64]);
+        key.chain_code.copy_from_slice(&bytes[64..]);
+        Ok(key)
+    }
+
+    /// Serialize as scalar, nonce and chain code.
+    pub fn to_bytes(&self) -> [u8; EXTENDED_SECRETKEY_SERIALIZED_LENGTH] {
+        let mut bytes = [0u8; EXTENDED_SECRETKEY_SERIALIZED_LENGTH];
+        bytes[..32].copy_from_slice(&self.scalar);
+        bytes[32..64].copy_from_slice(&self.nonce);
+        bytes[64..].copy_from_slice(&self.chain_code);
+        bytes
+    }
+
+    /// Derive the child key with given index, hardened if `index & HARDENED != 0`.
+    ///
+    /// With negligible probability, the child is the neutral element, and
+    /// `Error::IdentityChild` is returned: such indices must be skipped.
+    pub fn derive_child(&self, index: u32) -> Result<ExtendedSecretKey> {
+        let (z, chain_code) = if index & HARDENED != 0 {
+            (self.hmac(0x00, index), self.hmac(0x01, index))
+        } else {
+            let public_key = self.public_key();
+            (public_key.hmac(0x02, index), public_key.hmac(0x03, index))
+        };
+
+        let mut child = ExtendedSecretKey {
+            scalar: self.scalar,
+            nonce: self.nonce,
+            chain_code: [0u8; CHAIN_CODE_LENGTH],
+        };
+        child.chain_code.copy_from_slice(&chain_code[32..]);
+
+        // scalar + 8 * ZL, with ZL the first 28 bytes of Z
+        let mut eight_zl = [0u8; SECRETKEY_SCALAR_LENGTH];
+        eight_zl[..28].copy_from_slice(&z[..28]);
+        multiply_by_eight(&mut eight_zl);
+        add_assign(&mut child.scalar, &eight_zl);
+
+        // nonce + ZR
+        add_assign(&mut child.nonce, z[32..].try_into().unwrap());
+
+        eight_zl.zeroize();
+
+        // the paper discards children whose public key would be the identity
+        if Scalar(child.scalar).reduce() == Scalar::default() {
+            return Err(Error::IdentityChild);
+        }
+        Ok(child)
+    }
+
+    /// The chain code.
+    pub fn chain_code(&self) -> &[u8; CHAIN_CODE_LENGTH] {
+        &self.chain_code
+    }
+
+    /// The extended public key, for non-hardened derivation of public keys.
+    pub fn public_key(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            public_key: PublicKey::from(&self.secret_key()),
+            chain_code: self.chain_code,
+        }
+    }
+
+    /// The Ed25519 keypair of this extended key, without seed.
+    pub fn keypair(&self) -> Keypair {
+        Keypair::from(self.secret_key())
+    }
+
+    fn secret_key(&self) -> SecretKey {
+        SecretKey::from_scalar_and_nonce(Scalar(self.scalar), &self.nonce)
+    }
+
+    fn hmac(&self, prefix: u8, index: u32) -> [u8; SHA512_LENGTH] {
+        HmacSha512::new(&self.chain_code)
+            .updated(&[prefix])
+            .updated(&self.scalar)
+            .updated(&self.nonce)
+            .updated(&index.to_le_bytes())
+            .finalize()
+    }
+}
+
+impl ExtendedPublicKey {
+    /// Deserialize from public key and chain code.
+    pub fn from_bytes(
+        bytes: &[u8; EXTENDED_PUBLICKEY_SERIALIZED_LENGTH],
+    ) -> Result<ExtendedPublicKey> {
+        let public_key: [u8; PUBLICKEY_SERIALIZED_LENGTH] = bytes[..32].try_into().unwrap();
+        Ok(ExtendedPublicKey {
+            public_key: PublicKey::try_from(&public_key)?,
+            chain_code: bytes[32..].try_into().unwrap(),
+        })
+    }
+
+    /// Serialize as public key and chain code.
+    pub fn to_bytes(&self) -> [u8; EXTENDED_PUBLICKEY_SERIALIZED_LENGTH] {
+        let mut bytes = [0u8; EXTENDED_PUBLICKEY_SERIALIZED_LENGTH];
+        bytes[..32].copy_from_slice(self.public_key.as_bytes());
+        bytes[32..].copy_from_slice(&self.chain_code);
+        bytes
+    }
+
+    /// Derive the public key of the non-hardened child with given index.
+    ///
+    /// As for `ExtendedSecretKey::derive_child`, returns `Error::IdentityChild`
+    /// for the indices to skip.
+    pub fn derive_child(&self, index: u32) -> Result<ExtendedPublicKey> {
+        if index & HARDENED != 0 {
+            return Err(Error::HardenedPublicDerivation);
+        }
+
+        let z = self.hmac(0x02, index);
+        let chain_code = self.hmac(0x03, index);
+
+        // A + (8 * ZL) B, with ZL the first 28 bytes of Z
+        let mut eight_zl = Scalar::default();
+        eight_zl.0[..28].copy_from_slice(&z[..28]);
+        multiply_by_eight(&mut eight_zl.0);
+        let point = &self.public_key.point + &EdwardsPoint::mul_base(&eight_zl);
+
+        // as for secret derivation, discard children which are the identity
+        if point.is_identity() {
+            return Err(Error::IdentityChild);
+        }
+        Ok(ExtendedPublicKey {
+            public_key: PublicKey {
+                compressed: point.compressed(),
+                point,
+            },
+            chain_code: chain_code[32..].try_into().unwrap(),
+        })
+    }
+
+    /// The chain code.
+    pub fn chain_code(&self) -> &[u8; CHAIN_CODE_LENGTH] {
+        &self.chain_code
+    }
+
+    /// The Ed25519 public key of this extended key.
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key.clone()
+    }
+
+    fn hmac(&self, prefix: u8, index: u32) -> [u8; SHA512_LENGTH] {
+        HmacSha512::new(&self.chain_code)
+            .updated(&[prefix])
+            .updated(self.public_key.as_bytes())
+            .updated(&index.to_le_bytes())
+            .finalize()
+    }
+}
+
+/// little-endian multiplication by 8, where the top 3 bits are known to be zero
+fn multiply_by_eight(x: &mut [u8; 32]) {
+    let mut carry = 0u8;
+    for byte in x.iter_mut() {
+        let shifted_out = *byte >> 5;
+        *byte = (*byte << 3) | carry;
+        carry = shifted_out;
+    }
+}
+
+/// little-endian addition modulo 2^256
+fn add_assign(x: &mut [u8; 32], y: &[u8; 32]) {
+    let mut carry = 0u16;
+    for (a, b) in x.iter_mut().zip(y.iter()) {
+        let sum = *a as u16 + *b as u16 + carry;
+        *a = sum as u8;
+        carry = sum >> 8;
+    }
+}
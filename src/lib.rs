@@ -47,20 +47,21 @@ Thereafter, the signature can be checked:
 # let keypair: salty::Keypair = salty::Keypair::from(&seed);
 # let data: &[u8] = &[1, 2, 3]; // some data
 # let signature: salty::Signature = keypair.sign(data);
-let public_key: salty::PublicKey = keypair.public;
+let public_key: &salty::PublicKey = keypair.public();
 assert!(public_key.verify(data, &signature).is_ok());
 ```
 
 For serialization purposes, the entropic seed *is* the private key (32 bytes).
 Both public keys and signatures have `to_bytes()` methods, returning 32 and 64
-bytes, respectively.
+bytes, respectively. Keypairs serialize to the seed followed by the public key,
+as in NaCl and libsodium, via `Keypair::to_bytes` and `Keypair::from_bytes`.
 
 ```
 # let seed: [u8; 32] = [42; 32]; // 32 actually entropic bytes
 # let keypair: salty::Keypair = salty::Keypair::from(&seed);
 # let data: &[u8] = &[1, 2, 3]; // some data
 # let signature: salty::Signature = keypair.sign(data);
-# let public_key = &keypair.public;
+# let public_key = keypair.public();
 let serialized_public_key: [u8; 32] = public_key.to_bytes();
 let serialized_signature: [u8; 64] = signature.to_bytes();
 ```
@@ -73,7 +74,7 @@ lies on the curve), and `Signature` implements `From`.
 # let keypair: salty::Keypair = salty::Keypair::from(&seed);
 # let data: &[u8] = &[1, 2, 3]; // some data
 # let signature: salty::Signature = keypair.sign(data);
-# let public_key = &keypair.public;
+# let public_key = keypair.public();
 # let serialized_public_key: [u8; 32] = public_key.to_bytes();
 # let serialized_signature: [u8; 64] = signature.to_bytes();
 use core::convert::TryInto;
@@ -84,14 +85,26 @@ assert!(deserialized_public_key.verify(data, &deserialized_signature).is_ok());
 
 Please note that `Ed25519` signatures are *not* init-update-finalize signatures,
 since two passes over the data are made, sequentially (the output of the first pass
-is an input to the second pass).
+is an input to the second pass). Verification on the other hand only needs one pass,
+so `PublicKey::verifier` offers streaming verification of regular signatures.
+If the data can be read twice, `Keypair::sign_streaming` signs it in chunks.
 For cases where the data to be signed does not fit in memory, as explained in
 [RFC 8032](https://tools.ietf.org/html/rfc8032/) an alternative algorithm `Ed25519ph` ("ph" for prehashed) is
 defined. This is *not* the same as applying Ed25519 signature to the SHA512 hash of
 the data; it is is exposed via `Keypair::sign_prehashed` and
-`PublicKey::verify_prehashed`. Additionally, there is the option of using "contexts"
+`PublicKey::verify_prehashed`, or incrementally via `Keypair::prehash_signer` and
+`PublicKey::prehash_verifier`. Additionally, there is the option of using "contexts"
 for both regular and prehashed signatures.
 
+When verifying many signatures by the same key, a `PreparedPublicKey`
+precomputes tables of spaced multiples of the public key once, which saves most
+of the doublings, and makes each verification up to twice as fast.
+
+For protocols needing a group of prime order, the `ristretto` module implements
+Ristretto255 ([RFC 9496](https://www.rfc-editor.org/rfc/rfc9496)) on top of the Edwards curve.
+The `hash_to_curve` module maps byte strings to points on edwards25519 and curve25519,
+following [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380).
+
 ## Features
 The bulk of time generating and verifying signatures is spent with field operations
 in the base field of the underlying elliptic curve. This library has two implementations:
@@ -121,6 +134,15 @@ Current numbers on an NXP LPC55S69 running at 96Mhz, with "haase" implementation
 In both cases, we suggest compiling with at least minimal optimization, to get rid
 of the zero-cost abstractions.
 
+With the `rand_core` feature, `Keypair::sign_hedged` and its variants mix fresh randomness
+into the otherwise deterministic signatures, as protection against fault attacks.
+With the `fault-protection` feature, signing additionally checks the keypair and verifies
+each signature before returning it.
+
+Multiplications of the basepoint, used in key generation and signing, are sped up by
+precomputed tables. By default they take 768 bytes of flash; larger tables for faster
+signing can be selected with the `basepoint-table-6k` or `basepoint-table-24k` features.
+
 ## Future
 
 Future plans include:
@@ -136,6 +158,9 @@ Future plans include:
 // #[cfg(feature = "extern-panic-halt")]
 // extern crate panic_halt;
 
+#[cfg(test)]
+extern crate std;
+
 // use hex_literal::hex;
 
 /// Extensible error type for all `salty` operations.
@@ -157,13 +182,124 @@ pub enum Error {
     /// Signature verification failed
     SignatureInvalid,
 
-    /// Context for prehashed signatures too long
+    /// Context for signatures longer than 255 bytes
     ContextTooLong,
 
     /// Point is on other twist of curve
     WrongTwist,
+
+    /// Inputs to batch verification have different lengths
+    BatchLengthMismatch,
+
+    /// Scalar is not reduced modulo the group order
+    NonCanonicalScalar,
+
+    /// Public key bytes are not the canonical encoding of the point
+    NonCanonicalPublicKey,
+
+    /// Signature's R bytes are not the canonical encoding of the point
+    NonCanonicalR,
+
+    /// Public key is a point of small order
+    SmallOrderPublicKey,
+
+    /// Signature's R is a point of small order
+    SmallOrderR,
+
+    /// Message changed between the two passes of streaming signing
+    ReplayMismatch,
+
+    /// Public key does not belong to the secret key
+    PublicKeyMismatch,
+
+    /// Index for hardened-only key derivation does not have the hardened bit set
+    NonHardenedIndex,
+
+    /// Extended secret key bits violate the BIP32-Ed25519 constraints
+    InvalidExtendedSecretKey,
+
+    /// Hardened child keys can not be derived from public keys
+    HardenedPublicDerivation,
+
+    /// Public key has a component in the torsion subgroup
+    PublicKeyNotTorsionFree,
+
+    /// Signing was aborted, as a fault was detected
+    FaultDetected,
+
+    /// Compressed point does not decompress to a point on the curve
+    PointNotOnCurve,
+
+    /// Compressed point has x = 0, but its sign bit set
+    NegativeZero,
+
+    /// Bytes are not the canonical encoding of a Ristretto point
+    RistrettoBytesInvalid,
+
+    /// Requested output of `expand_message_xmd` is longer than 255 hash outputs
+    ExpandMessageTooLong,
+
+    /// Derived child key would be the neutral element, the index must be skipped
+    IdentityChild,
+
+    /// Scalars and points of a multiscalar multiplication have different lengths
+    MultiscalarLengthMismatch,
+
+    /// Scratch space has fewer entries than needed
+    ScratchTooSmall,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Error::NoError => "no error",
+            Error::NonCanonicalFieldElement => {
+                "bytes do not correspond to a canonical base field element"
+            }
+            Error::PublicKeyBytesInvalid => "public key bytes invalid",
+            Error::SignatureInvalid => "signature verification failed",
+            Error::ContextTooLong => "context longer than 255 bytes",
+            Error::WrongTwist => "point is on other twist of curve",
+            Error::BatchLengthMismatch => "inputs to batch verification have different lengths",
+            Error::NonCanonicalScalar => "scalar is not reduced modulo the group order",
+            Error::NonCanonicalPublicKey => {
+                "public key bytes are not the canonical encoding of the point"
+            }
+            Error::NonCanonicalR => {
+                "signature's R bytes are not the canonical encoding of the point"
+            }
+            Error::SmallOrderPublicKey => "public key is a point of small order",
+            Error::SmallOrderR => "signature's R is a point of small order",
+            Error::ReplayMismatch => "message changed between the two passes of streaming signing",
+            Error::PublicKeyMismatch => "public key does not belong to the secret key",
+            Error::NonHardenedIndex => "index for hardened-only key derivation is not hardened",
+            Error::InvalidExtendedSecretKey => {
+                "extended secret key bits violate the BIP32-Ed25519 constraints"
+            }
+            Error::HardenedPublicDerivation => {
+                "hardened child keys can not be derived from public keys"
+            }
+            Error::PublicKeyNotTorsionFree => "public key has a component in the torsion subgroup",
+            Error::FaultDetected => "signing was aborted, as a fault was detected",
+            Error::PointNotOnCurve => "compressed point is not on the curve",
+            Error::NegativeZero => "compressed point has x = 0, but its sign bit set",
+            Error::RistrettoBytesInvalid => {
+                "bytes are not the canonical encoding of a Ristretto point"
+            }
+            Error::ExpandMessageTooLong => {
+                "requested output of expand_message_xmd is longer than 255 hash outputs"
+            }
+            Error::IdentityChild => "derived child key would be the neutral element",
+            Error::MultiscalarLengthMismatch => {
+                "scalars and points of multiscalar multiplication have different lengths"
+            }
+            Error::ScratchTooSmall => "scratch space has fewer entries than needed",
+        })
+    }
 }
 
+impl core::error::Error for Error {}
+
 /// Result type for all `salty` operations.
 pub type Result<T = ()> = core::result::Result<T, Error>;
 
@@ -171,11 +307,14 @@ pub mod agreement;
 
 pub mod constants;
 
+pub mod hd;
+
 mod edwards;
-pub use edwards::{CompressedY, EdwardsPoint};
+pub use edwards::{CompressedY, EdwardsPoint, MultiscalarScratch, PIPPENGER_THRESHOLD};
 
 /// Self-contained implementation of SHA512
 mod hash;
+pub mod hash_to_curve;
 pub use hash::Sha512;
 
 /// Implementation of underlying curve base field arithmetic
@@ -190,9 +329,13 @@ mod scalar29;
 mod scalar;
 pub use scalar::Scalar;
 
+pub mod ristretto;
+
 pub mod signature;
 // TODO: rename these (and handle the API-breaking consequences)
 // It's confusing now that we have both Edwards and Montgomery points.
-pub use crate::signature::{Keypair, PublicKey, SecretKey, Signature};
+pub use crate::signature::{
+    verify_batch, Keypair, PreparedPublicKey, PublicKey, SecretKey, Signature,
+};
 #[cfg(feature = "cose")]
 pub use signature::CosePublicKey;
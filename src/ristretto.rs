@@ -0,0 +1,373 @@
+//! The Ristretto255 group of prime order `ell`, following [RFC 9496][rfc].
+//!
+//! Ristretto255 is built on top of the Edwards curve, which has cofactor eight:
+//! each Ristretto point is represented by an `EdwardsPoint`, and points differing
+//! by an element of the torsion subgroup of order four are identified. Encoding,
+//! decoding and equality are defined to respect this, so protocols built on
+//! `RistrettoPoint` don't need to deal with the cofactor.
+//!
+//! ```
+//! use salty::{ristretto::{CompressedRistretto, RistrettoPoint}, Scalar};
+//!
+//! let point = RistrettoPoint::mul_base(&Scalar::from(42));
+//! let compressed: CompressedRistretto = point.compressed();
+//! assert_eq!(compressed.decompressed().unwrap(), point);
+//!
+//! // hash-to-group, from 64 uniformly random bytes
+//! let hashed = RistrettoPoint::from_uniform_bytes(&salty::Sha512::new().updated(b"salty").finalize());
+//! assert_ne!(hashed, point);
+//! ```
+//!
+//! [rfc]: https://www.rfc-editor.org/rfc/rfc9496
+use core::ops::{Add, Mul, Neg, Sub};
+
+use subtle::{Choice, ConditionallyNegatable, ConditionallySelectable, ConstantTimeEq};
+
+use crate::{
+    edwards::EdwardsPoint,
+    field::{sqrt_ratio_i, FieldElement, FieldImplementation},
+    scalar::Scalar,
+    Error, Result,
+};
+
+/// length of a compressed Ristretto point
+pub const COMPRESSED_RISTRETTO_LENGTH: usize = 32;
+
+/// `1/sqrt(a - d)`, with `a = -1`
+const INVSQRT_A_MINUS_D: [u8; 32] = [
+    0xea, 0x40, 0x5d, 0x80, 0xaa, 0xfd, 0xc8, 0x99, 0xbe, 0x72, 0x41, 0x5a, 0x17, 0x16, 0x2f, 0x9d,
+    0x40, 0xd8, 0x01, 0xfe, 0x91, 0x7b, 0xc2, 0x16, 0xa2, 0xfc, 0xaf, 0xcf, 0x05, 0x89, 0x6c, 0x78,
+];
+
+/// `sqrt(a * d - 1)`, with `a = -1`
+const SQRT_AD_MINUS_ONE: [u8; 32] = [
+    0x1b, 0x2e, 0x7b, 0x49, 0xa0, 0xf6, 0x97, 0x7e, 0xbd, 0x54, 0x78, 0x1b, 0x0c, 0x8e, 0x9d, 0xaf,
+    0xfd, 0xd1, 0xf5, 0x31, 0xc9, 0xfc, 0x3c, 0x0f, 0xac, 0x48, 0x83, 0x2b, 0xbf, 0x31, 0x69, 0x37,
+];
+
+/// An element of the Ristretto255 group, represented by one of the
+/// four `EdwardsPoint`s in its coset.
+///
+/// As for `EdwardsPoint`, the group operations, compression and comparison
+/// run in constant time.
+#[derive(Clone, Copy, Debug)]
+pub struct RistrettoPoint(pub(crate) EdwardsPoint);
+
+/// The canonical encoding of a `RistrettoPoint`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CompressedRistretto(pub [u8; COMPRESSED_RISTRETTO_LENGTH]);
+
+/// The neutral element, not the all-zero coordinates of `EdwardsPoint::default()`,
+/// which are not a point, and would compare equal to every point.
+impl Default for RistrettoPoint {
+    fn default() -> Self {
+        RistrettoPoint::neutral_element()
+    }
+}
+
+impl From<&[u8; COMPRESSED_RISTRETTO_LENGTH]> for CompressedRistretto {
+    fn from(bytes: &[u8; COMPRESSED_RISTRETTO_LENGTH]) -> CompressedRistretto {
+        CompressedRistretto(*bytes)
+    }
+}
+
+impl CompressedRistretto {
+    pub fn as_bytes(&self) -> &[u8; COMPRESSED_RISTRETTO_LENGTH] {
+        &self.0
+    }
+
+    pub fn to_bytes(&self) -> [u8; COMPRESSED_RISTRETTO_LENGTH] {
+        self.0
+    }
+
+    /// Decode, following [RFC 9496, section 4.3.1][rfc].
+    ///
+    /// Only the canonical encodings produced by `RistrettoPoint::compressed` are accepted:
+    /// an unreduced field element is rejected with `Error::NonCanonicalFieldElement`,
+    /// all other invalid encodings with `Error::RistrettoBytesInvalid`.
+    /// Apart from the final check, this runs in constant time.
+    ///
+    /// [rfc]: https://www.rfc-editor.org/rfc/rfc9496#section-4.3.1
+    pub fn decompressed(&self) -> Result<RistrettoPoint> {
+        let s = FieldElement::from_bytes(&self.0)?;
+        let s_is_negative = Choice::from(s.parity());
+
+        let one = FieldElement::ONE;
+        let ss = s.squared();
+        let u1 = &one - &ss; // 1 + a * s^2
+        let u2 = &one + &ss; // 1 - a * s^2
+        let u2_squared = u2.squared();
+
+        // v = a * d * u1^2 - u2^2
+        let v = &(-&(&FieldElement::D * &u1.squared())) - &u2_squared;
+
+        let (was_square, invsqrt) = sqrt_ratio_i(&one, &(&v * &u2_squared));
+
+        let den_x = &invsqrt * &u2;
+        let den_y = &(&invsqrt * &den_x) * &v;
+
+        let mut x = &(&s + &s) * &den_x;
+        let x_is_negative = Choice::from(x.parity());
+        x.conditional_negate(x_is_negative);
+        let y = &u1 * &den_y;
+        let t = &x * &y;
+
+        let is_valid =
+            !s_is_negative & was_square & !Choice::from(t.parity()) & !y.ct_eq(&FieldElement::ZERO);
+
+        if bool::from(is_valid) {
+            Ok(RistrettoPoint(EdwardsPoint([x, y, one, t])))
+        } else {
+            Err(Error::RistrettoBytesInvalid)
+        }
+    }
+}
+
+impl RistrettoPoint {
+    /// The generator of the group, with the same representative as `EdwardsPoint::basepoint`.
+    pub fn basepoint() -> RistrettoPoint {
+        RistrettoPoint(EdwardsPoint::basepoint())
+    }
+
+    pub fn neutral_element() -> RistrettoPoint {
+        RistrettoPoint(EdwardsPoint::neutral_element())
+    }
+
+    /// Compute `scalar * basepoint`, using the precomputed basepoint tables.
+    pub fn mul_base(scalar: &Scalar) -> RistrettoPoint {
+        RistrettoPoint(EdwardsPoint::mul_base(scalar))
+    }
+
+    /// Encode, following [RFC 9496, section 4.3.2][rfc].
+    ///
+    /// The encoding does not depend on the representative of the coset.
+    ///
+    /// [rfc]: https://www.rfc-editor.org/rfc/rfc9496#section-4.3.2
+    pub fn compressed(&self) -> CompressedRistretto {
+        let [x0, y0, z0, t0] = &self.0 .0;
+
+        let u1 = &(z0 + y0) * &(z0 - y0);
+        let u2 = x0 * y0;
+
+        let (_, invsqrt) = sqrt_ratio_i(&FieldElement::ONE, &(&u1 * &u2.squared()));
+        let den1 = &invsqrt * &u1;
+        let den2 = &invsqrt * &u2;
+        let z_inv = &(&den1 * &den2) * t0;
+
+        let ix0 = x0 * &FieldElement::I;
+        let iy0 = y0 * &FieldElement::I;
+        let enchanted_denominator = &den1 * &FieldElement::from_bytes_unchecked(&INVSQRT_A_MINUS_D);
+
+        let rotate = Choice::from((t0 * &z_inv).parity());
+        let x = FieldElement::conditional_select(x0, &iy0, rotate);
+        let mut y = FieldElement::conditional_select(y0, &ix0, rotate);
+        let den_inv = FieldElement::conditional_select(&den2, &enchanted_denominator, rotate);
+
+        y.conditional_negate(Choice::from((&x * &z_inv).parity()));
+
+        let mut s = &den_inv * &(z0 - &y);
+        let s_is_negative = Choice::from(s.parity());
+        s.conditional_negate(s_is_negative);
+
+        CompressedRistretto(s.to_bytes())
+    }
+
+    /// Map 64 uniformly random bytes, e.g. the output of a hash function, to the group,
+    /// following [RFC 9496, section 4.3.4][rfc].
+    ///
+    /// The result is uniformly distributed, and its discrete logarithm is unknown.
+    ///
+    /// [rfc]: https://www.rfc-editor.org/rfc/rfc9496#section-4.3.4
+    pub fn from_uniform_bytes(bytes: &[u8; 64]) -> RistrettoPoint {
+        let p1 = elligator(&FieldElement::from_bytes_unchecked(
+            bytes[..32].try_into().unwrap(),
+        ));
+        let p2 = elligator(&FieldElement::from_bytes_unchecked(
+            bytes[32..].try_into().unwrap(),
+        ));
+        RistrettoPoint(&p1 + &p2)
+    }
+}
+
+/// The Elligator-based map `MAP` from a field element to a representative on the Edwards curve.
+fn elligator(t: &FieldElement) -> EdwardsPoint {
+    let one = FieldElement::ONE;
+    let minus_one = -&one;
+    let d = &FieldElement::D;
+    let one_minus_d_squared = &one - &d.squared();
+    let d_minus_one_squared = (d - &one).squared();
+
+    let r = &FieldElement::I * &t.squared();
+    let u = &(&r + &one) * &one_minus_d_squared;
+    let v = &(&minus_one - &(&r * d)) * &(&r + d);
+
+    let (was_square, mut s) = sqrt_ratio_i(&u, &v);
+
+    let mut s_prime = &s * t;
+    let s_prime_is_negative = Choice::from(s_prime.parity());
+    s_prime.conditional_negate(!s_prime_is_negative);
+
+    s.conditional_assign(&s_prime, !was_square);
+    let c = FieldElement::conditional_select(&r, &minus_one, was_square);
+
+    let n = &(&(&c * &(&r - &one)) * &d_minus_one_squared) - &v;
+
+    let w0 = &(&s + &s) * &v;
+    let w1 = &n * &FieldElement::from_bytes_unchecked(&SQRT_AD_MINUS_ONE);
+    let ss = s.squared();
+    let w2 = &one - &ss;
+    let w3 = &one + &ss;
+
+    EdwardsPoint([&w0 * &w3, &w2 * &w1, &w1 * &w3, &w0 * &w2])
+}
+
+impl Add<&RistrettoPoint> for &RistrettoPoint {
+    type Output = RistrettoPoint;
+
+    fn add(self, other: &RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint(&self.0 + &other.0)
+    }
+}
+
+impl Sub<&RistrettoPoint> for &RistrettoPoint {
+    type Output = RistrettoPoint;
+
+    fn sub(self, other: &RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint(&self.0 - &other.0)
+    }
+}
+
+impl Neg for &RistrettoPoint {
+    type Output = RistrettoPoint;
+
+    fn neg(self) -> RistrettoPoint {
+        RistrettoPoint(-&self.0)
+    }
+}
+
+impl Mul<&RistrettoPoint> for &Scalar {
+    type Output = RistrettoPoint;
+
+    fn mul(self, point: &RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint(self * &point.0)
+    }
+}
+
+impl Mul<&Scalar> for &RistrettoPoint {
+    type Output = RistrettoPoint;
+
+    fn mul(self, scalar: &Scalar) -> RistrettoPoint {
+        RistrettoPoint(scalar * &self.0)
+    }
+}
+
+impl ConditionallySelectable for RistrettoPoint {
+    fn conditional_select(p: &Self, q: &Self, choice: Choice) -> Self {
+        RistrettoPoint(EdwardsPoint::conditional_select(&p.0, &q.0, choice))
+    }
+}
+
+impl ConstantTimeEq for CompressedRistretto {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl PartialEq for CompressedRistretto {
+    fn eq(&self, other: &Self) -> bool {
+        bool::from(self.ct_eq(other))
+    }
+}
+
+impl ConstantTimeEq for RistrettoPoint {
+    /// Compare cosets, following [RFC 9496, section 4.3.3][rfc],
+    /// without normalizing the projective coordinates.
+    ///
+    /// [rfc]: https://www.rfc-editor.org/rfc/rfc9496#section-4.3.3
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let [x1, y1, _, _] = &self.0 .0;
+        let [x2, y2, _, _] = &other.0 .0;
+
+        (x1 * y2).ct_eq(&(y1 * x2)) | (y1 * y2).ct_eq(&(x1 * x2))
+    }
+}
+
+impl PartialEq for RistrettoPoint {
+    fn eq(&self, other: &Self) -> bool {
+        bool::from(self.ct_eq(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the points of order four, and the neutral element
+    fn torsion_four() -> [EdwardsPoint; 4] {
+        let zero = FieldElement::ZERO;
+        let one = FieldElement::ONE;
+        let i = FieldElement::I;
+        [
+            EdwardsPoint::neutral_element(),
+            EdwardsPoint([i, zero, one, zero]),
+            EdwardsPoint([zero, -&one, one, zero]),
+            EdwardsPoint([-&i, zero, one, zero]),
+        ]
+    }
+
+    #[test]
+    fn coset_representatives() {
+        let point = RistrettoPoint::mul_base(&Scalar::from(0x5a17));
+        let compressed = point.compressed();
+
+        for torsion in torsion_four() {
+            assert!(torsion.is_small_order());
+            let representative = RistrettoPoint(&point.0 + &torsion);
+            assert_eq!(representative, point);
+            assert_eq!(representative.compressed(), compressed);
+        }
+
+        let other = &point + &RistrettoPoint::basepoint();
+        assert_ne!(other, point);
+        assert_ne!(other.compressed(), compressed);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut point = RistrettoPoint::neutral_element();
+        for _ in 0..16 {
+            let decompressed = point.compressed().decompressed().unwrap();
+            assert_eq!(decompressed, point);
+            assert_eq!(decompressed.compressed(), point.compressed());
+            point = &point + &RistrettoPoint::basepoint();
+        }
+    }
+
+    #[test]
+    fn group_api() {
+        let a = Scalar::from(1234);
+        let b = Scalar::from(5678);
+        let p = RistrettoPoint::mul_base(&a);
+        let q = &RistrettoPoint::basepoint() * &b;
+
+        assert_eq!(&a * &RistrettoPoint::basepoint(), p);
+        assert_eq!(&p + &q, RistrettoPoint::mul_base(&(&a + &b)));
+        assert_eq!(&(&p - &q) + &q, p);
+        assert_eq!(&p + &-&p, RistrettoPoint::neutral_element());
+        assert_eq!(
+            RistrettoPoint::conditional_select(&p, &q, Choice::from(1)),
+            q
+        );
+    }
+
+    #[test]
+    fn default_is_neutral() {
+        let default = RistrettoPoint::default();
+        assert_eq!(default, RistrettoPoint::neutral_element());
+        assert_ne!(default, RistrettoPoint::mul_base(&Scalar::from(5)));
+        assert_eq!(
+            default.compressed(),
+            RistrettoPoint::neutral_element().compressed()
+        );
+    }
+}
@@ -216,6 +216,126 @@ impl Scalar {
         bits
     }
 
+    /// Signed radix 16 digits `a[i]` of the scalar, with `-8 <= a[i] < 8` except for
+    /// the last digit, such that the scalar equals `sum(a[i] * 16^i)`.
+    ///
+    /// This is constant-time. The scalar must be below `2^255`, which holds for
+    /// reduced scalars, and then the last digit is at most 8.
+    pub(crate) fn as_radix_16(&self) -> [i8; 64] {
+        debug_assert!(self.0[31] <= 127);
+
+        let mut digits = [0i8; 64];
+        for (i, byte) in self.0.iter().enumerate() {
+            digits[2 * i] = (byte & 0xf) as i8;
+            digits[2 * i + 1] = (byte >> 4) as i8;
+        }
+
+        // move digits from [0, 16) to [-8, 8), carrying into the next digit
+        for i in 0..63 {
+            let carry = (digits[i] + 8) >> 4;
+            digits[i] -= carry << 4;
+            digits[i + 1] += carry;
+        }
+
+        digits
+    }
+
+    /// Signed radix `2^w` digits `a[i]` of the scalar, for `4 <= w <= 7`, with
+    /// `-2^(w-1) <= a[i] < 2^(w-1)` except for the last digit, such that the scalar
+    /// equals `sum(a[i] * 2^(w*i))`. Only the first `ceil(256 / w)` digits are used.
+    ///
+    /// The scalar must be below `2^255`, which holds for reduced scalars.
+    pub(crate) fn as_radix_2w(&self, w: usize) -> [i8; 256] {
+        debug_assert!((4..=7).contains(&w));
+        debug_assert!(self.0[31] <= 127);
+
+        let mut x = [0u64; 5];
+        for (i, limb) in x.iter_mut().take(4).enumerate() {
+            *limb = u64::from_le_bytes(self.0[8 * i..][..8].try_into().unwrap());
+        }
+
+        let radix = 1u64 << w;
+        let window_mask = radix - 1;
+        let digits_count = 256usize.div_ceil(w);
+
+        let mut digits = [0i8; 256];
+        let mut carry = 0u64;
+        for (i, digit) in digits.iter_mut().take(digits_count).enumerate() {
+            // read the next w bits, which may straddle two limbs
+            let index = (i * w) / 64;
+            let offset = (i * w) % 64;
+            let bits = if offset < 64 - w {
+                x[index] >> offset
+            } else {
+                (x[index] >> offset) | (x[index + 1] << (64 - offset))
+            };
+
+            // move the window from [0, 2^w) to [-2^(w-1), 2^(w-1)), carrying into the next one
+            let window = carry + (bits & window_mask);
+            carry = (window + radix / 2) >> w;
+            *digit = (window as i64 - (carry << w) as i64) as i8;
+        }
+
+        // as the scalar is below 2^255, the last window has room for the carry
+        digits[digits_count - 1] += (carry << w) as i8;
+
+        digits
+    }
+
+    /// Width-`w` non-adjacent form of the scalar: digits that are either zero or odd
+    /// with absolute value below `2^(w-1)`, any `w` consecutive ones having at most
+    /// one non-zero digit, such that the scalar equals `sum(naf[i] * 2^i)`.
+    ///
+    /// This is *not* constant-time, only use it on public scalars.
+    /// The scalar must be below `2^255`, which holds for reduced scalars.
+    pub(crate) fn non_adjacent_form(&self, w: usize) -> [i8; 256] {
+        debug_assert!((2..=8).contains(&w));
+        debug_assert!(self.0[31] <= 127);
+
+        let mut naf = [0i8; 256];
+
+        let mut x = [0u64; 5];
+        for (i, limb) in x.iter_mut().take(4).enumerate() {
+            *limb = u64::from_le_bytes(self.0[8 * i..][..8].try_into().unwrap());
+        }
+
+        let width = 1u64 << w;
+        let window_mask = width - 1;
+
+        let mut pos = 0;
+        let mut carry = 0;
+        while pos < 256 {
+            // read the next w bits, which may straddle two limbs
+            let index = pos / 64;
+            let offset = pos % 64;
+            let bits = if offset < 64 - w {
+                x[index] >> offset
+            } else {
+                (x[index] >> offset) | (x[index + 1] << (64 - offset))
+            };
+
+            let window = carry + (bits & window_mask);
+
+            if window & 1 == 0 {
+                // the window has the form 0bxxxx0 and cannot contribute a digit at `pos`
+                pos += 1;
+                continue;
+            }
+
+            if window < width / 2 {
+                carry = 0;
+                naf[pos] = window as i8;
+            } else {
+                carry = 1;
+                naf[pos] = (window as i16 - width as i16) as i8;
+            }
+
+            pos += w;
+        }
+
+        naf
+    }
+
     pub fn from_u256_le(x: &U256le) -> Scalar {
         // TweetNaclScalar::from(&Scalar(*x)).reduce_modulo_ell()
         // Temporarily allow s_unreduced.bytes > 2^255 ...
@@ -312,4 +432,97 @@ mod test {
 
         assert_eq!(one.0, [0u8; SCALAR_LENGTH]);
     }
+
+    #[test]
+    fn non_adjacent_form() {
+        let scalar = Scalar::from_u512_le(&[0xa5; 64]);
+
+        for w in 2..=8 {
+            let naf = scalar.non_adjacent_form(w);
+
+            // digits are odd and bounded, and separated by at least w - 1 zeros
+            let mut last = None;
+            for (i, digit) in naf.iter().enumerate().filter(|(_, digit)| **digit != 0) {
+                assert_eq!(digit & 1, 1);
+                assert!((*digit as i16).abs() < 1 << (w - 1));
+                if let Some(last) = last {
+                    assert!(i - last >= w);
+                }
+                last = Some(i);
+            }
+
+            // the digits sum up to the scalar
+            let mut sum = [0i64; 33];
+            for (i, digit) in naf.iter().enumerate() {
+                sum[i / 8] += (*digit as i64) << (i % 8);
+            }
+            for i in 0..32 {
+                let carry = sum[i] >> 8;
+                sum[i] -= carry << 8;
+                sum[i + 1] += carry;
+            }
+            assert_eq!(sum[32], 0);
+            assert!(sum
+                .iter()
+                .zip(scalar.0.iter())
+                .all(|(a, b)| *a == *b as i64));
+        }
+    }
+
+    #[test]
+    fn radix_16() {
+        let scalar = Scalar::from_u512_le(&[0xa5; 64]);
+        let digits = scalar.as_radix_16();
+
+        assert!(digits[..63].iter().all(|digit| (-8..8).contains(digit)));
+        assert!((-8..=8).contains(&digits[63]));
+
+        // the digits sum up to the scalar
+        let mut sum = [0i64; 33];
+        for (i, digit) in digits.iter().enumerate() {
+            sum[i / 2] += (*digit as i64) << (4 * (i % 2));
+        }
+        for i in 0..32 {
+            let carry = sum[i] >> 8;
+            sum[i] -= carry << 8;
+            sum[i + 1] += carry;
+        }
+        assert_eq!(sum[32], 0);
+        assert!(sum
+            .iter()
+            .zip(scalar.0.iter())
+            .all(|(a, b)| *a == *b as i64));
+    }
+
+    #[test]
+    fn radix_2w() {
+        let scalar = Scalar::from_u512_le(&[0xa5; 64]);
+
+        for w in 4..=7 {
+            let digits = scalar.as_radix_2w(w);
+            let digits_count = 256usize.div_ceil(w);
+
+            let bound = 1i8 << (w - 1);
+            assert!(digits[..digits_count - 1]
+                .iter()
+                .all(|digit| (-bound..bound).contains(digit)));
+            assert!(digits[digits_count..].iter().all(|digit| *digit == 0));
+
+            // the digits sum up to the scalar
+            let mut sum = [0i64; 34];
+            for (i, digit) in digits[..digits_count].iter().enumerate() {
+                sum[(w * i) / 8] += (*digit as i64) << ((w * i) % 8);
+            }
+            for i in 0..33 {
+                let carry = sum[i] >> 8;
+                sum[i] -= carry << 8;
+                sum[i + 1] += carry;
+            }
+            assert_eq!(sum[32..], [0, 0]);
+            assert!(sum
+                .iter()
+                .zip(scalar.0.iter())
+                .all(|(a, b)| *a == *b as i64));
+        }
+    }
 }
@@ -0,0 +1,145 @@
+use hex_literal::hex;
+use salty::hd::{
+    bip32_ed25519::{ExtendedPublicKey, ExtendedSecretKey},
+    HARDENED,
+};
+
+// Root key of the `cardano-serialization-lib` test wallet "art forum devote street sure
+// rather head chuckle guard poverty release quote oak craft enemy", as scalar, nonce and
+// chain code, with its public key (from the `xprv_128_test` test of `crypto.rs`).
+const CARDANO_ROOT: [u8; 96] = hex!("b8f2bece9bdfe2b0282f5bad705562ac996efb6af96b648f4445ec44f47ad95c10e3d72f26ed075422a36ed8585c745a0e1150bcceba2357d058636991f38a3791e248de509c070d812ab2fda57860ac876bc489192c1ef4ce253c197ee219a4");
+const CARDANO_ROOT_PUBLIC_KEY: [u8; 32] =
+    hex!("cf76399a210de8720e9fa894e45e41e29ab525e30bc402801c076250d1585bcd");
+
+// Account m/1852'/1815'/0' of the above wallet.
+const CARDANO_ACCOUNT: [u32; 3] = [1852 | HARDENED, 1815 | HARDENED, HARDENED];
+
+// Public keys of the spending key m/1852'/1815'/0'/0/0 and the staking key m/1852'/1815'/0'/2/0.
+// Their Blake2b-224 hashes are the key hashes in the address
+// "addr1q9u5vlrf4xkxv2qpwngf6cjhtw542ayty80v8dyr49rf5ewvxwdrt70qlcpeeagscasafhffqsxy36t90ldv06wqrk2qld6xc3"
+// of the `bip32_15_base` test of `address.rs`.
+const CARDANO_CHILDREN: [([u32; 2], [u8; 32]); 2] = [
+    (
+        [0, 0],
+        hex!("489ef28ea97f719ee7768645fc74b811c271e5d7ef06c2310854db30158e945d"),
+    ),
+    (
+        [2, 0],
+        hex!("13fe0ab7d1fd4cbb55508c755829219d77432f9dc26c9955a632cbcbe30cfa34"),
+    ),
+];
+
+// `D1`, its hardened child `D1_H0` and the latter's signature of "Hello World",
+// from the tests of the `ed25519-bip32` crate.
+const D1: [u8; 96] = hex!("f8a29231ee38d6c5bf715d5bac21c750577aa3798b22d79d65bf97d6fadea15adcd1ee1abdf78bd4be64731a12deb94d3671784112eb6f364b871851fd1c9a247384db9ad6003bbd08b3b1ddc0d07a597293ff85e961bf252b331262eddfad0d");
+const D1_H0: [u8; 96] = hex!("60d399da83ef80d8d4f8d223239efdc2b8fef387e1b5219137ffb4e8fbdea15adc9366b7d003af37c11396de9a83734e30e05e851efa32745c9cd7b42712c890608763770eddf77248ab652984b21b849760d1da74a6f5bd633ce41adceef07a");
+const D1_H0_SIGNATURE: [u8; 64] = hex!("90194d57cde4fdadd01eb7cf161780c277e129fc7135b97779a3268837e4cd2e9444b9bb91c0e84d23bba870df3c4bda91a110ef735638fa7a34ea2046d4be04");
+
+fn cardano_account() -> ExtendedSecretKey {
+    let mut key = ExtendedSecretKey::from_bytes(&CARDANO_ROOT).unwrap();
+    for index in CARDANO_ACCOUNT.iter() {
+        key = key.derive_child(*index).unwrap();
+    }
+    key
+}
+
+#[test]
+fn bip32_ed25519_root() {
+    let root = ExtendedSecretKey::from_bytes(&CARDANO_ROOT).unwrap();
+    assert_eq!(root.to_bytes(), CARDANO_ROOT);
+    assert_eq!(root.chain_code()[..], CARDANO_ROOT[64..]);
+    assert_eq!(root.keypair().public().to_bytes(), CARDANO_ROOT_PUBLIC_KEY);
+    assert_eq!(
+        root.public_key().public_key().to_bytes(),
+        CARDANO_ROOT_PUBLIC_KEY
+    );
+
+    // the root key material is already clamped
+    let mut key = [0u8; 64];
+    key.copy_from_slice(&CARDANO_ROOT[..64]);
+    let mut chain_code = [0u8; 32];
+    chain_code.copy_from_slice(&CARDANO_ROOT[64..]);
+    assert_eq!(
+        ExtendedSecretKey::from_root_key(&key, &chain_code)
+            .unwrap()
+            .to_bytes(),
+        CARDANO_ROOT
+    );
+}
+
+#[test]
+fn bip32_ed25519_hardened_derivation() {
+    let key = ExtendedSecretKey::from_bytes(&D1).unwrap();
+    let child = key.derive_child(HARDENED).unwrap();
+    assert_eq!(child.to_bytes(), D1_H0);
+
+    // signing uses the scalar and nonce directly
+    let keypair = child.keypair();
+    let signature = keypair.sign(b"Hello World");
+    assert_eq!(signature.to_bytes(), D1_H0_SIGNATURE);
+    assert!(keypair.public().verify(b"Hello World", &signature).is_ok());
+}
+
+#[test]
+fn bip32_ed25519_secret_derivation() {
+    let account = cardano_account();
+
+    for (path, public_key) in CARDANO_CHILDREN.iter() {
+        let mut key = account.clone();
+        for index in path.iter() {
+            key = key.derive_child(*index).unwrap();
+        }
+        assert_eq!(key.keypair().public().to_bytes(), *public_key);
+        assert_eq!(
+            ExtendedSecretKey::from_bytes(&key.to_bytes())
+                .unwrap()
+                .to_bytes(),
+            key.to_bytes()
+        );
+    }
+}
+
+#[test]
+fn bip32_ed25519_public_derivation() {
+    let account = cardano_account();
+    let account_public = ExtendedPublicKey::from_bytes(&account.public_key().to_bytes()).unwrap();
+    assert_eq!(account_public, account.public_key());
+
+    for (path, public_key) in CARDANO_CHILDREN.iter() {
+        let mut secret = account.clone();
+        let mut key = account_public.clone();
+        for index in path.iter() {
+            secret = secret.derive_child(*index).unwrap();
+            key = key.derive_child(*index).unwrap();
+            assert_eq!(key, secret.public_key());
+        }
+        assert_eq!(key.public_key().to_bytes(), *public_key);
+
+        assert_eq!(
+            key.derive_child(HARDENED).err(),
+            Some(salty::Error::HardenedPublicDerivation)
+        );
+    }
+}
+
+#[test]
+fn bip32_ed25519_invalid_keys() {
+    // the third highest bit of root keys must be cleared
+    let mut key = [0u8; 64];
+    key.copy_from_slice(&CARDANO_ROOT[..64]);
+    key[31] |= 0b0010_0000;
+    let mut chain_code = [0u8; 32];
+    chain_code.copy_from_slice(&CARDANO_ROOT[64..]);
+    assert_eq!(
+        ExtendedSecretKey::from_root_key(&key, &chain_code).err(),
+        Some(salty::Error::InvalidExtendedSecretKey)
+    );
+
+    // scalars must be multiples of the cofactor, below 2^255
+    let mut bytes = D1;
+    bytes[0] |= 1;
+    assert!(ExtendedSecretKey::from_bytes(&bytes).is_err());
+    let mut bytes = D1;
+    bytes[31] |= 0x80;
+    assert!(ExtendedSecretKey::from_bytes(&bytes).is_err());
+}
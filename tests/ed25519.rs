@@ -21,27 +21,28 @@ mod wycheproof {
         let expected_sig = <[u8; SIGNATURE_SERIALIZED_LENGTH]>::try_from(test_data.sig)
             .map(|arr| Signature::from(&arr));
 
-        let valid = match (pk, sk, expected_sig) {
+        let (valid, strictly_valid) = match (pk, sk, expected_sig) {
             (Ok(Ok(pk)), Ok(sk), Ok(expected_sig)) => {
                 let result = pk.verify(test_data.msg, &expected_sig);
-                let kp = Keypair {
-                    secret: sk,
-                    public: pk,
-                };
+                let strict_result = pk.verify_strict(test_data.msg, &expected_sig);
+                let kp = Keypair::from_parts(sk, pk).unwrap();
                 let sig = kp.sign(test_data.msg);
-                result.is_ok() && sig.to_bytes() == test_data.sig
+                (
+                    result.is_ok() && sig.to_bytes() == test_data.sig,
+                    strict_result.is_ok(),
+                )
             }
-            _ => false,
+            _ => (false, false),
         };
 
         match test_data.result {
-            ExpectedResult::Valid => assert!(valid),
+            ExpectedResult::Valid => assert!(valid && strictly_valid),
             ExpectedResult::Invalid => {
-                if test_data.flags.contains(&"SignatureMalleability") {
-                    // accept failing SignatureMalleability tests
-                } else {
+                // only strict verification rejects SignatureMalleability tests
+                if !test_data.flags.contains(&"SignatureMalleability") {
                     assert!(!valid)
                 }
+                assert!(!strictly_valid)
             }
             ExpectedResult::Acceptable => {}
         }
@@ -0,0 +1,140 @@
+//! Test vectors from [RFC 9380, appendix J.4 and J.5](https://www.rfc-editor.org/rfc/rfc9380#appendix-J.4).
+//!
+//! For the curve25519 suites, only the u-coordinates `P.x` are checked,
+//! as `MontgomeryPoint` does not hold the v-coordinate.
+
+use salty::{
+    hash_to_curve::{
+        CURVE25519_XMD_SHA512_ELL2_NU, CURVE25519_XMD_SHA512_ELL2_RO,
+        EDWARDS25519_XMD_SHA512_ELL2_NU, EDWARDS25519_XMD_SHA512_ELL2_RO,
+    },
+    EdwardsPoint, FieldElement, FieldImplementation, MontgomeryPoint,
+};
+
+/// the messages "", "abc", "abcdef0123456789", "q128_" followed by 128 "q",
+/// and "a512_" followed by 512 "a"
+fn messages() -> [Vec<u8>; 5] {
+    [
+        b"".to_vec(),
+        b"abc".to_vec(),
+        b"abcdef0123456789".to_vec(),
+        [&b"q128_"[..], &[b'q'; 128]].concat(),
+        [&b"a512_"[..], &[b'a'; 512]].concat(),
+    ]
+}
+
+/// `(P.x, P.y)` for the suite `edwards25519_XMD:SHA-512_ELL2_RO_`
+const EDWARDS25519_RO: [(&str, &str); 5] = [
+    (
+        "3c3da6925a3c3c268448dcabb47ccde5439559d9599646a8260e47b1e4822fc6",
+        "09a6c8561a0b22bef63124c588ce4c62ea83a3c899763af26d795302e115dc21",
+    ),
+    (
+        "608040b42285cc0d72cbb3985c6b04c935370c7361f4b7fbdb1ae7f8c1a8ecad",
+        "1a8395b88338f22e435bbd301183e7f20a5f9de643f11882fb237f88268a5531",
+    ),
+    (
+        "6d7fabf47a2dc03fe7d47f7dddd21082c5fb8f86743cd020f3fb147d57161472",
+        "53060a3d140e7fbcda641ed3cf42c88a75411e648a1add71217f70ea8ec561a6",
+    ),
+    (
+        "5fb0b92acedd16f3bcb0ef83f5c7b7a9466b5f1e0d8d217421878ea3686f8524",
+        "2eca15e355fcfa39d2982f67ddb0eea138e2994f5956ed37b7f72eea5e89d2f7",
+    ),
+    (
+        "0efcfde5898a839b00997fbe40d2ebe950bc81181afbd5cd6b9618aa336c1e8c",
+        "6dc2fc04f266c5c27f236a80b14f92ccd051ef1ff027f26a07f8c0f327d8f995",
+    ),
+];
+
+/// `(P.x, P.y)` for the suite `edwards25519_XMD:SHA-512_ELL2_NU_`
+const EDWARDS25519_NU: [(&str, &str); 5] = [
+    (
+        "1ff2b70ecf862799e11b7ae744e3489aa058ce805dd323a936375a84695e76da",
+        "222e314d04a4d5725e9f2aff9fb2a6b69ef375a1214eb19021ceab2d687f0f9b",
+    ),
+    (
+        "5f13cc69c891d86927eb37bd4afc6672360007c63f68a33ab423a3aa040fd2a8",
+        "67732d50f9a26f73111dd1ed5dba225614e538599db58ba30aaea1f5c827fa42",
+    ),
+    (
+        "1dd2fefce934ecfd7aae6ec998de088d7dd03316aa1847198aecf699ba6613f1",
+        "2f8a6c24dd1adde73909cada6a4a137577b0f179d336685c4a955a0a8e1a86fb",
+    ),
+    (
+        "35fbdc5143e8a97afd3096f2b843e07df72e15bfca2eaf6879bf97c5d3362f73",
+        "2af6ff6ef5ebba128b0774f4296cb4c2279a074658b083b8dcca91f57a603450",
+    ),
+    (
+        "6e5e1f37e99345887fc12111575fc1c3e36df4b289b8759d23af14d774b66bff",
+        "2c90c3d39eb18ff291d33441b35f3262cdd307162cc97c31bfcc7a4245891a37",
+    ),
+];
+
+/// `P.x` for the suite `curve25519_XMD:SHA-512_ELL2_RO_`
+const CURVE25519_RO: [&str; 5] = [
+    "2de3780abb67e861289f5749d16d3e217ffa722192d16bbd9d1bfb9d112b98c0",
+    "2b4419f1f2d48f5872de692b0aca72cc7b0a60915dd70bde432e826b6abc526d",
+    "68ca1ea5a6acf4e9956daa101709b1eee6c1bb0df1de3b90d4602382a104c036",
+    "096e9c8bae6c06b554c1ee69383bb0e82267e064236b3a30608d4ed20b73ac5a",
+    "1bc61845a138e912f047b5e70ba9606ba2a447a4dade024c8ef3dd42b7bbc5fe",
+];
+
+/// `P.x` for the suite `curve25519_XMD:SHA-512_ELL2_NU_`
+const CURVE25519_NU: [&str; 5] = [
+    "1bb913f0c9daefa0b3375378ffa534bda5526c97391952a7789eb976edfe4d08",
+    "7c22950b7d900fa866334262fcaea47a441a578df43b894b4625c9b450f9a026",
+    "31ad08a8b0deeb2a4d8b0206ca25f567ab4e042746f792f4b7973f3ae2096c52",
+    "027877759d155b1997d0d84683a313eb78bdb493271d935b622900459d52ceaa",
+    "5fd892c0958d1a75f54c3182a18d286efab784e774d1e017ba2fb252998b5dc1",
+];
+
+fn field_element(be_hex: &str) -> FieldElement {
+    let mut bytes: [u8; 32] = hex::decode(be_hex).unwrap().try_into().unwrap();
+    bytes.reverse();
+    FieldElement::from_bytes(&bytes).unwrap()
+}
+
+fn dst(suite: &str) -> Vec<u8> {
+    [&b"QUUX-V01-CS02-with-"[..], suite.as_bytes()].concat()
+}
+
+fn check_edwards(point: EdwardsPoint, (x, y): (&str, &str)) {
+    assert_eq!(point.x(), field_element(x));
+    assert_eq!(point.y(), field_element(y));
+    assert!(point.is_torsion_free());
+}
+
+#[test]
+fn edwards25519_hash_to_curve() {
+    let dst = dst(EDWARDS25519_XMD_SHA512_ELL2_RO);
+    for (msg, expected) in messages().iter().zip(EDWARDS25519_RO) {
+        check_edwards(EdwardsPoint::hash_to_curve(msg, &dst), expected);
+    }
+}
+
+#[test]
+fn edwards25519_encode_to_curve() {
+    let dst = dst(EDWARDS25519_XMD_SHA512_ELL2_NU);
+    for (msg, expected) in messages().iter().zip(EDWARDS25519_NU) {
+        check_edwards(EdwardsPoint::encode_to_curve(msg, &dst), expected);
+    }
+}
+
+#[test]
+fn curve25519_hash_to_curve() {
+    let dst = dst(CURVE25519_XMD_SHA512_ELL2_RO);
+    for (msg, expected) in messages().iter().zip(CURVE25519_RO) {
+        let point = MontgomeryPoint::hash_to_curve(msg, &dst);
+        assert_eq!(point, MontgomeryPoint(field_element(expected)));
+    }
+}
+
+#[test]
+fn curve25519_encode_to_curve() {
+    let dst = dst(CURVE25519_XMD_SHA512_ELL2_NU);
+    for (msg, expected) in messages().iter().zip(CURVE25519_NU) {
+        let point = MontgomeryPoint::encode_to_curve(msg, &dst);
+        assert_eq!(point, MontgomeryPoint(field_element(expected)));
+    }
+}
@@ -0,0 +1,45 @@
+use salty::{EdwardsPoint, MultiscalarScratch, Scalar, PIPPENGER_THRESHOLD};
+
+/// points `P_i = (i + 1) B`, so the expected result is `(sum (i + 1) a_i) B`
+fn check(n: usize) {
+    let scalars: Vec<Scalar> = (0..n)
+        .map(|i| Scalar::from_u512_le(&[i as u8 ^ 0xa5; 64]))
+        .collect();
+
+    let basepoint = EdwardsPoint::basepoint();
+    let mut points = vec![basepoint];
+    for i in 1..n {
+        points.push(&points[i - 1] + &basepoint);
+    }
+
+    let mut coefficient = Scalar::default();
+    for (i, scalar) in scalars.iter().enumerate() {
+        coefficient = &coefficient + &(&Scalar::from(i as u64 + 1) * scalar);
+    }
+    let expected = EdwardsPoint::mul_base(&coefficient);
+
+    let mut scratch = vec![MultiscalarScratch::default(); n];
+    assert_eq!(
+        EdwardsPoint::vartime_multiscalar_mul(&scalars, &points, &mut scratch),
+        Ok(expected)
+    );
+    assert_eq!(
+        EdwardsPoint::multiscalar_mul(&scalars, &points, &mut scratch),
+        Ok(expected)
+    );
+}
+
+#[test]
+fn straus() {
+    check(1);
+    check(64);
+    check(PIPPENGER_THRESHOLD - 1);
+}
+
+#[test]
+fn pippenger() {
+    check(PIPPENGER_THRESHOLD);
+    check(499);
+    check(500);
+    check(700);
+}
@@ -0,0 +1,71 @@
+//! Test vectors from [ZIP-215](https://zips.z.cash/zip-0215).
+//!
+//! Each pair of the following encodings of points of small order,
+//! as public key `A` and signature point `R`, together with `s = 0`,
+//! is a valid signature on the message "Zcash".
+
+use salty::{PublicKey, Signature};
+
+const SMALL_ORDER_ENCODINGS: [&str; 14] = [
+    // canonical encodings of the eight points of small order
+    "0100000000000000000000000000000000000000000000000000000000000000",
+    "c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac037a",
+    "0000000000000000000000000000000000000000000000000000000000000080",
+    "26e8958fc2b227b045c3f489f2ef98f0d5dfac05d3c63339b13802886d53fc05",
+    "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    "26e8958fc2b227b045c3f489f2ef98f0d5dfac05d3c63339b13802886d53fc85",
+    "0000000000000000000000000000000000000000000000000000000000000000",
+    "c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac03fa",
+    // non-canonical encodings
+    "0100000000000000000000000000000000000000000000000000000000000080",
+    "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+    "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+    "eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    "eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+];
+
+fn load_bytes(hex_digits: &str) -> [u8; 32] {
+    hex::decode(hex_digits).unwrap().try_into().unwrap()
+}
+
+#[test]
+fn zip215_small_order_test_vectors() {
+    for a in SMALL_ORDER_ENCODINGS {
+        let public_key = PublicKey::try_from(&load_bytes(a)).unwrap();
+
+        for r in SMALL_ORDER_ENCODINGS {
+            let mut signature = [0u8; 64];
+            signature[..32].copy_from_slice(&load_bytes(r));
+            let signature = Signature::from(&signature);
+
+            assert!(
+                public_key.verify_zip215(b"Zcash", &signature).is_ok(),
+                "A = {}, R = {}",
+                a,
+                r
+            );
+        }
+    }
+}
+
+#[test]
+fn zip215_agrees_on_regular_signatures() {
+    let keypair = salty::Keypair::from(&[7u8; 32]);
+    let signature = keypair.sign(b"Zcash");
+
+    assert!(keypair.public().verify_zip215(b"Zcash", &signature).is_ok());
+    assert!(keypair
+        .public()
+        .verify_zip215(b"zcash", &signature)
+        .is_err());
+
+    // the scalar must be reduced
+    let mut malleated = signature.to_bytes();
+    malleated[63] |= 0x80;
+    let malleated = Signature::from(&malleated);
+    assert_eq!(
+        keypair.public().verify_zip215(b"Zcash", &malleated),
+        Err(salty::Error::NonCanonicalScalar)
+    );
+}